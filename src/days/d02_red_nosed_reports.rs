@@ -2,7 +2,7 @@ use Kind::*;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, parse_number, read_lines},
 };
 
 #[derive(Copy, Clone, Debug)]
@@ -27,19 +27,27 @@ impl Kind {
     }
 }
 
-fn p1_count_safe(filename: &str) -> usize {
-    read_lines(filename)
-        .map(|line| find_unsafe(line.split_whitespace().flat_map(str::parse::<i32>)).is_none())
+fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, AocError> {
+    read_lines(input)
+        .enumerate()
+        .map(|(y, line)| line.split_whitespace().map(|s| parse_number(y, line, s)).collect())
+        .collect()
+}
+
+fn p1_count_safe(input: &str) -> Result<usize, AocError> {
+    Ok(parse_reports(input)?
+        .into_iter()
+        .map(|v| find_unsafe(v.into_iter()).is_none())
         .filter(|&x| x)
-        .count()
+        .count())
 }
 
-fn p2_count_safe_allow_error(filename: &str) -> usize {
-    read_lines(filename)
-        .map(|line| line.split_whitespace().flat_map(str::parse::<i32>).collect::<Vec<_>>())
+fn p2_count_safe_allow_error(input: &str) -> Result<usize, AocError> {
+    Ok(parse_reports(input)?
+        .into_iter()
         .map(|v| with_error_allowed(&v, None))
         .filter(|&x| x)
-        .count()
+        .count())
 }
 
 fn find_unsafe(xs: impl Iterator<Item = i32>) -> Option<usize> {
@@ -68,32 +76,14 @@ fn with_error_allowed(xs: &[i32], skip: Option<usize>) -> bool {
     }
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 2,
-    part_1: { examples: ["example.txt"], func: p1_count_safe },
-    part_2: { examples: ["example.txt"], func: p2_count_safe_allow_error }
-};
+day! { 2: Day<usize, usize>,
+    part_1: { examples: ["example.txt" => 2], func: p1_count_safe },
+    part_2: { examples: ["example.txt" => 4], func: p2_count_safe_allow_error }
+}
 
 #[cfg(test)]
 mod d02_playground {
-
     #[test]
     #[ignore]
     fn playground() {}
 }
-
-#[cfg(test)]
-mod d02_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 2);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        let res = SOLUTION.part_2.run_example(0);
-        assert_eq!(res, 4);
-    }
-}
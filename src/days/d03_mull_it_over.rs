@@ -1,6 +1,6 @@
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, read_lines},
 };
 
 fn parse_mul(s: &str, l_br: usize) -> Option<u32> {
@@ -12,9 +12,9 @@ fn parse_mul(s: &str, l_br: usize) -> Option<u32> {
     Some(a * b)
 }
 
-fn p1_sum_of_mul(ss: impl Iterator<Item = String>) -> u32 {
+fn p1_sum_of_mul(ss: impl Iterator<Item = &str>) -> u32 {
     ss.map(|line| {
-        std::iter::successors(Some((line.as_str(), None)), |&(suffix, _)| {
+        std::iter::successors(Some((line, None)), |&(suffix, _)| {
             let l_br = suffix.find("mul(")? + 3;
             Some((&suffix[l_br + 1..], parse_mul(suffix, l_br)))
         })
@@ -24,7 +24,7 @@ fn p1_sum_of_mul(ss: impl Iterator<Item = String>) -> u32 {
     .sum()
 }
 
-fn p2_sum_of_mul_enable(ss: impl Iterator<Item = String>) -> u32 {
+fn p2_sum_of_mul_enable(ss: impl Iterator<Item = &str>) -> u32 {
     ss.scan(true, |enabled, line| {
         let res = (0..line.len())
             .flat_map(|i| {
@@ -46,35 +46,21 @@ fn p2_sum_of_mul_enable(ss: impl Iterator<Item = String>) -> u32 {
     .sum()
 }
 
-fn p1(filename: &str) -> u32 {
-    p1_sum_of_mul(read_lines(filename))
+fn p1(input: &str) -> Result<u32, AocError> {
+    Ok(p1_sum_of_mul(read_lines(input)))
 }
 
-fn p2(filename: &str) -> u32 {
-    p2_sum_of_mul_enable(read_lines(filename))
+fn p2(input: &str) -> Result<u32, AocError> {
+    Ok(p2_sum_of_mul_enable(read_lines(input)))
 }
 
-pub const SOLUTION: Day<u32, u32> = day! { 3,
-    part_1: { examples: ["example_1.txt"], func: p1 },
-    part_2: { examples: ["example_2.txt"], func: p2 }
-};
+day! { 3: Day<u32, u32>,
+    part_1: { examples: ["example_1.txt" => 161], func: p1 },
+    part_2: { examples: ["example_2.txt" => 48], func: p2 }
+}
 
 #[cfg(test)]
 mod d03_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 161);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        let res = SOLUTION.part_2.run_example(0);
-        assert_eq!(res, 48);
-    }
-
     #[test]
     fn foo() {}
 }
@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, parse_number, read_lines},
 };
 
 fn check_nums(nums: &[u32], cmp: &HashMap<(u32, u32), Ordering>) -> bool {
@@ -22,60 +22,53 @@ fn check_nums(nums: &[u32], cmp: &HashMap<(u32, u32), Ordering>) -> bool {
     true
 }
 
-fn cmp_map(ss: &mut impl Iterator<Item = String>) -> HashMap<(u32, u32), Ordering> {
-    std::iter::from_fn(|| ss.next().filter(|line| !line.is_empty()))
-        .flat_map(|s| {
-            let (lhs, rhs) = s.split_once('|')?;
-            Some((lhs.parse::<u32>().ok()?, rhs.parse::<u32>().ok()?))
+fn cmp_map(
+    ss: &mut impl Iterator<Item = (usize, &str)>,
+) -> Result<HashMap<(u32, u32), Ordering>, AocError> {
+    std::iter::from_fn(|| ss.next().filter(|(_, line)| !line.is_empty()))
+        .map(|(y, s)| {
+            let (lhs, rhs) =
+                s.split_once('|').ok_or_else(|| AocError::expected("'|'").on_line(y))?;
+            Ok((parse_number(y, s, lhs)?, parse_number(y, s, rhs)?))
         })
-        .fold(HashMap::new(), |mut cmp, (lhs, rhs)| {
+        .try_fold(HashMap::new(), |mut cmp, pair: Result<_, AocError>| {
+            let (lhs, rhs) = pair?;
             cmp.insert((lhs, rhs), Ordering::Less);
             cmp.insert((rhs, lhs), Ordering::Greater);
-            cmp
+            Ok(cmp)
         })
 }
 
-fn p1(filename: &str) -> u32 {
-    let mut ss = read_lines(filename);
-    let cmp = cmp_map(&mut ss);
+fn parse_updates(ss: impl Iterator<Item = (usize, &str)>) -> Result<Vec<Vec<u32>>, AocError> {
+    ss.map(|(y, line)| line.split(',').map(|s| parse_number(y, line, s)).collect()).collect()
+}
+
+fn p1(input: &str) -> Result<u32, AocError> {
+    let mut ss = read_lines(input).enumerate();
+    let cmp = cmp_map(&mut ss)?;
 
-    ss.map(|line| line.split(',').flat_map(str::parse::<u32>).collect::<Vec<_>>())
+    Ok(parse_updates(ss)?
+        .into_iter()
         .filter(|nums| check_nums(nums, &cmp))
         .map(|nums| nums[nums.len() / 2])
-        .sum()
+        .sum())
 }
 
-fn p2(filename: &str) -> u32 {
-    let mut ss = read_lines(filename);
-    let cmp = cmp_map(&mut ss);
+fn p2(input: &str) -> Result<u32, AocError> {
+    let mut ss = read_lines(input).enumerate();
+    let cmp = cmp_map(&mut ss)?;
 
-    ss.map(|line| line.split(',').flat_map(str::parse::<u32>).collect::<Vec<_>>())
+    Ok(parse_updates(ss)?
+        .into_iter()
         .filter(|nums| !check_nums(nums, &cmp))
         .map(|mut nums| {
             nums.sort_by(|&a, &b| *cmp.get(&(a, b)).unwrap_or(&Ordering::Equal));
             nums[nums.len() / 2]
         })
-        .sum()
+        .sum())
 }
 
-pub const SOLUTION: Day<u32, u32> = day! { 5,
-    part_1: { examples: ["example.txt"], func: p1 },
-    part_2: { examples: ["example.txt"], func: p2 }
-};
-
-#[cfg(test)]
-mod d05_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 143);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        let res = SOLUTION.part_2.run_example(0);
-        assert_eq!(res, 123);
-    }
+day! { 5: Day<u32, u32>,
+    part_1: { examples: ["example.txt" => 143], func: p1 },
+    part_2: { examples: ["example.txt" => 123], func: p2 }
 }
@@ -4,153 +4,100 @@ use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{
+        AocError,
+        Day,
+        check_cancelled,
+        geometry::{Direction, Point},
+        grid::Grid,
+    },
 };
 
 #[rustfmt::skip]
 #[derive(Copy, Clone)]
 enum Tile { Empty, Obstacle }
 
-#[rustfmt::skip]
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-enum Direction { U, R, D, L }
-
-impl Direction {
-    fn from_char(c: char) -> Self {
-        match c {
-            '^' => Self::U,
-            '>' => Self::R,
-            'v' => Self::D,
-            '<' => Self::L,
-            _ => unreachable!(),
-        }
-    }
-}
-
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 struct Guard {
-    x: usize,
-    y: usize,
+    pos: Point<usize>,
     dir: Direction,
 }
 
 impl Guard {
-    fn new(x: usize, y: usize, dir: Direction) -> Self {
-        Self { x, y, dir }
+    fn new(pos: Point<usize>, dir: Direction) -> Self {
+        Self { pos, dir }
     }
 
-    fn advance(self, tiles: &[Vec<Tile>]) -> Option<Self> {
-        use Direction::*;
-        let (w, h) = (tiles[0].len(), tiles.len());
-        let Self { x, y, dir } = self;
-
-        match self.dir {
-            U if self.y == 0 => None,
-            U => match tiles[y - 1][x] {
-                Tile::Empty => Some(Self::new(x, y - 1, dir)),
-                Tile::Obstacle => Some(Self::new(x, y, R)),
-            },
-            R if x + 1 == w => None,
-            R => match tiles[y][x + 1] {
-                Tile::Empty => Some(Self::new(x + 1, y, dir)),
-                Tile::Obstacle => Some(Self::new(x, y, D)),
-            },
-            D if y + 1 == h => None,
-            D => match tiles[y + 1][x] {
-                Tile::Empty => Some(Self::new(x, y + 1, dir)),
-                Tile::Obstacle => Some(Self::new(x, y, L)),
-            },
-            L if x == 0 => None,
-            L => match tiles[y][x - 1] {
-                Tile::Empty => Some(Self::new(x - 1, y, dir)),
-                Tile::Obstacle => Some(Self::new(x, y, U)),
-            },
+    fn advance(self, tiles: &Grid<Tile>) -> Option<Self> {
+        let Self { pos, dir } = self;
+
+        let next = pos.step(dir, tiles.size())?;
+        match tiles[next] {
+            Tile::Empty => Some(Self::new(next, dir)),
+            Tile::Obstacle => Some(Self::new(pos, dir.turn_right())),
         }
     }
 }
 
-fn parse_input(filename: &str) -> (Vec<Vec<Tile>>, Guard) {
-    let mut guard = Guard::new(0, 0, Direction::U);
-
-    let tiles = read_lines(filename)
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    '.' => Tile::Empty,
-                    '^' | '>' | 'v' | '<' => {
-                        guard = Guard::new(x, y, Direction::from_char(c));
-                        Tile::Empty
-                    }
-                    '#' => Tile::Obstacle,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
+fn parse_input(input: &str) -> Result<(Grid<Tile>, Guard), AocError> {
+    const GUARDS: [char; 4] = ['^', '>', 'v', '<'];
 
-    (tiles, guard)
+    let (tiles, guards) = Grid::parse_with_markers(input, GUARDS, |c| match c {
+        '.' => Some(Tile::Empty),
+        '#' => Some(Tile::Obstacle),
+        _ => Direction::from_char(c).map(|_| Tile::Empty),
+    })?;
+
+    let guard = std::iter::zip(guards, GUARDS)
+        .find_map(|(xy, c)| Some(Guard::new(xy?, Direction::from_char(c)?)))
+        .ok_or_else(|| AocError::expected("a guard ('^', '>', 'v' or '<')"))?;
+    Ok((tiles, guard))
+}
+
+// The guard never leaves a map that already traps them in a loop, hence the cancellation check.
+fn walk(start: Guard, tiles: &Grid<Tile>) -> impl Iterator<Item = Result<Guard, AocError>> {
+    std::iter::successors(Some(start), |g| g.advance(tiles)).map(|g| check_cancelled().map(|_| g))
 }
 
-fn p1(filename: &str) -> usize {
-    let (tiles, guard) = parse_input(filename);
+fn p1(input: &str) -> Result<usize, AocError> {
+    let (tiles, guard) = parse_input(input)?;
     p1_path_length(tiles, guard)
 }
 
-fn p1_path_length(tiles: Vec<Vec<Tile>>, guard: Guard) -> usize {
-    std::iter::successors(Some(guard), |g| g.advance(&tiles)).map(|g| (g.x, g.y)).unique().count()
+fn p1_path_length(tiles: Grid<Tile>, guard: Guard) -> Result<usize, AocError> {
+    walk(guard, &tiles).process_results(|guards| guards.map(|g| g.pos).unique().count())
 }
 
-fn p2(filename: &str) -> usize {
-    let (mut tiles, guard) = parse_input(filename);
+fn p2(input: &str) -> Result<usize, AocError> {
+    let (mut tiles, guard) = parse_input(input)?;
     p2_add_obstacle(&mut tiles, guard)
 }
 
-fn p2_add_obstacle(tiles: &mut [Vec<Tile>], start: Guard) -> usize {
-    let path: Vec<_> = std::iter::successors(Some(start), |g| g.advance(tiles)).collect();
+fn p2_add_obstacle(tiles: &mut Grid<Tile>, start: Guard) -> Result<usize, AocError> {
+    let path: Vec<_> = walk(start, tiles).try_collect()?;
 
     path.iter()
         .tuple_windows::<(_, _)>()
-        .scan(HashSet::<(usize, usize)>::new(), |visited, (&g1, &g2)| {
-            match visited.insert((g2.x, g2.y)) && g2 != start {
-                true => Some(Some((g1, g2))),
-                false => Some(None),
-            }
+        .scan(HashSet::new(), |visited, (&g1, &g2)| match visited.insert(g2.pos) && g2 != start {
+            true => Some(Some((g1, g2))),
+            false => Some(None),
         })
         .flatten()
-        .filter(|&(g1, g2)| {
-            tiles[g2.y][g2.x] = Tile::Obstacle;
+        .try_fold(0, |count, (g1, g2)| {
+            check_cancelled()?;
+            tiles[g2.pos] = Tile::Obstacle;
             let stuck = std::iter::successors(Some(g1), |g| g.advance(tiles))
                 .try_fold(HashSet::new(), |mut loop_visited, g| match loop_visited.insert(g) {
                     true => Some(loop_visited),
                     false => None,
                 })
                 .is_none();
-            tiles[g2.y][g2.x] = Tile::Empty;
-            stuck
+            tiles[g2.pos] = Tile::Empty;
+            Ok(count + usize::from(stuck))
         })
-        .count()
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 6,
-    part_1: { examples: ["example.txt"], func: p1 },
-    part_2: { examples: ["example.txt"], func: p2 }
-};
-
-#[cfg(test)]
-mod d06_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 41);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        let res = SOLUTION.part_2.run_example(0);
-        assert_eq!(res, 6);
-    }
+day! { 6: Day<usize, usize>,
+    part_1: { examples: ["example.txt" => 41], func: p1 },
+    part_2: { examples: ["example.txt" => 6], func: p2 }
 }
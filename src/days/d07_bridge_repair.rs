@@ -1,30 +1,42 @@
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, check_cancelled, parse_number, read_lines},
 };
 
-fn parse_file(filename: &str) -> impl Iterator<Item = (u64, Vec<u64>)> {
-    read_lines(filename).flat_map(|line| {
-        let (v, xs) = line.split_once(':')?;
-        let target: u64 = v.parse().ok()?;
-        let xs: Vec<u64> = xs.split_whitespace().flat_map(str::parse).collect();
-        Some((target, xs))
-    })
+fn parse_file(input: &str) -> Result<Vec<(u64, Vec<u64>)>, AocError> {
+    read_lines(input)
+        .enumerate()
+        .map(|(y, line)| {
+            let (v, xs) =
+                line.split_once(':').ok_or_else(|| AocError::expected("':'").on_line(y))?;
+            let target: u64 = parse_number(y, line, v)?;
+            let xs = xs
+                .split_whitespace()
+                .map(|x| parse_number(y, line, x))
+                .collect::<Result<Vec<u64>, _>>()?;
+            match xs.is_empty() {
+                true => Err(AocError::expected("at least one number").on_line(y)),
+                false => Ok((target, xs)),
+            }
+        })
+        .collect()
 }
 
-fn p1_two_ops(filename: &str) -> u64 {
-    parse_file(filename)
-        .filter_map(|(target, xs)| {
+fn p1_two_ops(input: &str) -> Result<u64, AocError> {
+    parse_file(input)?
+        .into_iter()
+        .map(|(target, xs)| {
+            check_cancelled()?;
             let gaps = xs.len() - 1;
-            (0..2u64.pow(gaps as u32))
+            let solvable = (0..2u64.pow(gaps as u32))
                 .map(|ops| {
                     (0..gaps).fold(xs[0], |acc, i| match ops & (1 << i) {
                         0 => acc + xs[i + 1],
                         _ => acc * xs[i + 1],
                     })
                 })
-                .any(|res| res == target)
-                .then_some(target)
+                .any(|res| res == target);
+            Ok(if solvable { target } else { 0 })
         })
         .sum()
 }
@@ -34,11 +46,13 @@ fn next_power_of_ten(x: u64) -> u64 {
     std::iter::successors(Some(10), |&v| (v <= x).then_some(10 * v)).last().unwrap()
 }
 
-fn p2_three_ops(filename: &str) -> u64 {
-    parse_file(filename)
-        .filter_map(|(target, xs)| {
+fn p2_three_ops(input: &str) -> Result<u64, AocError> {
+    parse_file(input)?
+        .into_iter()
+        .map(|(target, xs)| {
+            check_cancelled()?;
             let gaps = xs.len() - 1;
-            (0..3u64.pow(gaps as u32))
+            let solvable = (0..3u64.pow(gaps as u32))
                 .flat_map(|ops| {
                     std::iter::successors(Some(ops), |&v| Some(v / 3))
                         .take(gaps)
@@ -54,30 +68,13 @@ fn p2_three_ops(filename: &str) -> u64 {
                             _ => unreachable!(),
                         })
                 })
-                .any(|res| res == target)
-                .then_some(target)
+                .any(|res| res == target);
+            Ok(if solvable { target } else { 0 })
         })
         .sum()
 }
 
-pub const SOLUTION: Day<u64, u64> = day! { 7,
-    part_1: { examples: ["example.txt"], func: p1_two_ops },
-    part_2: { examples: ["example.txt"], func: p2_three_ops }
-};
-
-#[cfg(test)]
-mod d07_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 3749);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        let res = SOLUTION.part_2.run_example(0);
-        assert_eq!(res, 11387);
-    }
+day! { 7: Day<u64, u64>,
+    part_1: { examples: ["example.txt" => 3749], func: p1_two_ops },
+    part_2: { examples: ["example.txt" => 11387], func: p2_three_ops }
 }
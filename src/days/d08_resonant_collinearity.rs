@@ -4,13 +4,13 @@ use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, read_lines},
 };
 
 #[allow(clippy::type_complexity)]
-fn parse_file(filename: &str) -> (HashMap<char, Vec<(i32, i32)>>, i32, i32) {
+fn parse_file(input: &str) -> Result<(HashMap<char, Vec<(i32, i32)>>, i32, i32), AocError> {
     let (mut w, mut h) = (0, 0);
-    let map = read_lines(filename)
+    let map = read_lines(input)
         .enumerate()
         .flat_map(|(y, line)| {
             (w, h) = (line.len() as i32, y as i32);
@@ -25,31 +25,33 @@ fn parse_file(filename: &str) -> (HashMap<char, Vec<(i32, i32)>>, i32, i32) {
             map
         });
 
-    (map, w, h + 1)
+    Ok((map, w, h + 1))
 }
 
 fn distinct_pairs<T: Copy>(arr: &[T]) -> impl Iterator<Item = (T, T)> {
     arr.iter().enumerate().flat_map(|(i, &x)| arr[i + 1..].iter().map(move |&y| (x, y)))
 }
 
-fn p1(filename: &str) -> usize {
-    let (antennas, w, h) = parse_file(filename);
-    antennas
+fn p1(input: &str) -> Result<usize, AocError> {
+    let (antennas, w, h) = parse_file(input)?;
+    let res = antennas
         .values()
         .flat_map(|xys| distinct_pairs(xys))
         .flat_map(|((x1, y1), (x2, y2))| [(2 * x1 - x2, 2 * y1 - y2), (2 * x2 - x1, 2 * y2 - y1)])
         .filter(|(x, y)| (0..w).contains(x) && (0..h).contains(y))
         .unique()
-        .count()
+        .count();
+
+    Ok(res)
 }
 
-fn p2(filename: &str) -> usize {
-    let (antennas, w, h) = parse_file(filename);
+fn p2(input: &str) -> Result<usize, AocError> {
+    let (antennas, w, h) = parse_file(input)?;
     let draw_line = |(x, y), (dx, dy)| {
         std::iter::successors(Some((x, y)), move |&(x, y)| Some((x + dx, y + dy)))
             .take_while(|(x, y)| (0..w).contains(x) && (0..h).contains(y))
     };
-    antennas
+    let res = antennas
         .values()
         .flat_map(|xys| distinct_pairs(xys))
         .flat_map(|((x1, y1), (x2, y2))| {
@@ -59,30 +61,12 @@ fn p2(filename: &str) -> usize {
             )
         })
         .unique()
-        .count()
-}
-
-pub const SOLUTION: Day<usize, usize> = day! { 8,
-    part_1: { examples: ["example_p1_1.txt", "example_p1_2.txt"], func: p1 },
-    part_2: { examples: ["example_p2.txt"], func: p2 }
-};
+        .count();
 
-#[cfg(test)]
-mod d08_tests {
-    use super::*;
-
-    #[test]
-    fn part_1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 14);
-
-        let res = SOLUTION.part_1.run_example(1);
-        assert_eq!(res, 4);
-    }
+    Ok(res)
+}
 
-    #[test]
-    fn part_2_example_test() {
-        let res = SOLUTION.part_2.run_example(0);
-        assert_eq!(res, 9);
-    }
+day! { 8: Day<usize, usize>,
+    part_1: { examples: ["example_p1_1.txt" => 14, "example_p1_2.txt" => 4], func: p1 },
+    part_2: { examples: ["example_p2.txt" => 9], func: p2 }
 }
@@ -4,13 +4,22 @@ use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, read_lines},
 };
 
-fn p1(filename: &str) -> usize {
-    let mut arr = read_lines(filename)
+fn parse_disk_map(input: &str) -> Result<&str, AocError> {
+    let line = read_lines(input)
         .next()
-        .unwrap()
+        .filter(|line| !line.is_empty())
+        .ok_or_else(|| AocError::expected("a disk map"))?;
+    match line.chars().position(|c| !c.is_ascii_digit()) {
+        Some(x) => Err(AocError::unexpected_char(0, x, line[x..].chars().next().unwrap())),
+        None => Ok(line),
+    }
+}
+
+fn p1(input: &str) -> Result<usize, AocError> {
+    let mut arr = parse_disk_map(input)?
         .chars()
         .flat_map(|c| c.to_digit(10))
         .enumerate()
@@ -20,18 +29,19 @@ fn p1(filename: &str) -> usize {
         })
         .collect_vec();
 
+    // `rt` is one past the last block that can still be moved.
     let mut lt = 0;
-    let mut rt = arr.len() - 1;
+    let mut rt = arr.len();
 
     loop {
         lt += arr[lt..].iter().take_while(|x| x.is_some()).count();
-        rt -= arr[..=rt].iter().rev().take_while(|x| x.is_none()).count();
+        rt -= arr[..rt].iter().rev().take_while(|x| x.is_none()).count();
 
-        if lt >= rt {
-            return arr.iter().flatten().enumerate().map(|(i, &x)| i * x).sum();
+        if lt + 1 >= rt {
+            return Ok(arr.iter().flatten().enumerate().map(|(i, &x)| i * x).sum());
         }
 
-        arr.swap(lt, rt);
+        arr.swap(lt, rt - 1);
         lt += 1;
         rt -= 1;
     }
@@ -76,11 +86,9 @@ fn p2_(line: &str) -> usize {
             (BinaryHeap::new(), vec![BinaryHeap::new(); 10], 0),
             |(mut files, mut empty, pos), (i, len)| {
                 match i % 2 == 0 {
-                    true => files.push(File { id: i / 2, pos, len }),
-                    false if pos > 0 => {
-                        empty[len].push(Gap(pos));
-                    }
-                    _ => {}
+                    true if len > 0 => files.push(File { id: i / 2, pos, len }),
+                    false => empty[len].push(Gap(pos)),
+                    true => {}
                 };
                 (files, empty, pos + len)
             },
@@ -112,47 +120,40 @@ fn p2_(line: &str) -> usize {
     res
 }
 
-fn p2(filename: &str) -> usize {
-    p2_(&read_lines(filename).next().unwrap())
+fn p2(input: &str) -> Result<usize, AocError> {
+    Ok(p2_(parse_disk_map(input)?))
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 9,
-    part_1: { examples: ["example_1.txt", "example_2.txt"], func: p1 },
-    part_2: { examples: ["example_2.txt", "example_3.txt"], func: p2 }
-};
+day! { 9: Day<usize, usize>,
+    part_1: { examples: ["example_1.txt" => 60, "example_2.txt" => 1928], func: p1 },
+    part_2: { examples: ["example_2.txt" => 2858, "example_3.txt" => 2900], func: p2 }
+}
 
 #[cfg(test)]
 mod d09_tests {
     use std::hint::black_box;
 
     use super::*;
+    use crate::utils::{input_path, read_input};
 
     extern crate test;
     use test::bench::Bencher;
 
     #[test]
-    fn p1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 60);
-        let res = SOLUTION.part_1.run_example(1);
-        assert_eq!(res, 1928);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        // 6408966547049 yay!
-        let res = SOLUTION.part_2.run_example(0);
-        assert_eq!(res, 2858);
-
-        let res = SOLUTION.part_2.run_example(1);
-        assert_eq!(res, 2900);
+    fn empty_files() {
+        assert_eq!(p1("0").unwrap(), 0);
+        assert_eq!(p1("02").unwrap(), 0);
+        assert_eq!(p1("0211").unwrap(), 0);
+        assert_eq!(p2("0").unwrap(), 0);
+        assert_eq!(p2("0211").unwrap(), 0);
     }
 
     #[bench]
     fn p2_1(b: &mut Bencher) {
-        let line = read_lines("./inputs/day_09/task.txt").next().unwrap();
+        let input = read_input(input_path(SOLUTION.part_2.task)).unwrap();
+        let line = parse_disk_map(&input).unwrap();
         //1426371
 
-        b.iter(|| black_box(p2_(&line)));
+        b.iter(|| black_box(p2_(line)));
     }
 }
@@ -2,11 +2,12 @@ use std::collections::HashMap;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, parse_number, read_lines},
 };
 
-fn parse_numbers(filename: &str) -> Vec<usize> {
-    read_lines(filename).next().unwrap().split_whitespace().flat_map(str::parse::<usize>).collect()
+fn parse_numbers(input: &str) -> Result<Vec<usize>, AocError> {
+    let line = read_lines(input).next().ok_or_else(|| AocError::expected("a line of numbers"))?;
+    line.split_whitespace().map(|s| parse_number(0, line, s)).collect()
 }
 
 fn split_number(n: usize) -> Option<(usize, usize)> {
@@ -39,30 +40,16 @@ fn make_blinks(ns: Vec<usize>, blinks: usize) -> usize {
     ns.into_iter().map(|n| req(n, 0, blinks, &mut memo)).sum()
 }
 
-fn p1(filename: &str) -> usize {
-    make_blinks(parse_numbers(filename), 25)
+fn p1(input: &str) -> Result<usize, AocError> {
+    Ok(make_blinks(parse_numbers(input)?, 25))
 }
-fn p2(filename: &str) -> usize {
-    make_blinks(parse_numbers(filename), 75)
+fn p2(input: &str) -> Result<usize, AocError> {
+    Ok(make_blinks(parse_numbers(input)?, 75))
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 11,
-    part_1: { examples: ["example_2.txt"], func: p1 },
+day! { 11: Day<usize, usize>,
+    part_1: { examples: ["example_2.txt" => 55312], func: p1 },
     part_2: { examples: ["example_2.txt"], func: p2 }
-};
-
-#[cfg(test)]
-mod d11_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        let res = SOLUTION.part_1.run_example(0);
-        assert_eq!(res, 55312);
-    }
-
-    #[test]
-    fn p2_example_test() {}
 }
 
 #[cfg(test)]
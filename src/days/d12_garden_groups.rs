@@ -1,46 +1,25 @@
-use std::collections::VecDeque;
-
 use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, ParsedDay, geometry::Point, grid::Grid, search::bfs},
 };
 
 type Intervals = Vec<(usize, usize)>;
+type Regions = Vec<(char, Vec<(usize, Intervals)>)>;
 
 fn extract_area(
-    (x0, y0): (usize, usize),
-    map: &mut [Vec<Option<char>>],
+    start: Point<usize>,
+    map: &mut Grid<Option<char>>,
 ) -> Option<Vec<(usize, Intervals)>> {
-    let c = map[y0][x0]?;
-    let (w, h) = (map[0].len(), map.len());
-
-    let mut points = vec![];
-    let mut q = VecDeque::new();
-    q.push_back((x0, y0));
-
-    while let Some((x, y)) = q.pop_front() {
-        if map[y][x] != Some(c) {
-            continue;
-        }
-        map[y][x].take();
+    let c = map[start]?;
 
-        points.push((y, x));
-
-        if x > 0 {
-            q.push_back((x - 1, y));
-        }
-        if x + 1 < w {
-            q.push_back((x + 1, y));
-        }
-        if y > 0 {
-            q.push_back((x, y - 1));
-        }
-        if y + 1 < h {
-            q.push_back((x, y + 1));
-        }
-    }
+    let plants = &*map;
+    let mut points: Vec<_> =
+        bfs([start], |&xy| xy.neighbours(plants.size()).filter(|&next| plants[next] == Some(c)))
+            .map(|(Point { x, y }, _)| (y, x))
+            .collect();
+    points.iter().for_each(|&(y, x)| map[Point::new(x, y)] = None);
 
     points.sort();
 
@@ -133,18 +112,15 @@ fn sides_change(a: &[(usize, usize)], b: &[(usize, usize)]) -> usize {
     res
 }
 
-fn parse_intervals(filename: &str) -> impl Iterator<Item = (char, Vec<(usize, Intervals)>)> {
-    let mut map: Vec<Vec<_>> =
-        read_lines(filename).map(|line| line.chars().map(Some).collect()).collect();
-    let (w, h) = (map[0].len(), map.len());
+fn parse_regions(input: &str) -> Result<Regions, AocError> {
+    let mut map = Grid::parse(input, |c| Some(Some(c)))?;
 
-    (0..h)
-        .flat_map(move |y| (0..w).map(move |x| (x, y)))
-        .flat_map(move |(x, y)| Some((map[y][x]?, extract_area((x, y), &mut map)?)))
+    Ok(map.positions().flat_map(|xy| Some((map[xy]?, extract_area(xy, &mut map)?))).collect())
 }
 
-fn p1(filename: &str) -> usize {
-    parse_intervals(filename)
+fn p1(regions: &Regions) -> Result<usize, AocError> {
+    let res = regions
+        .iter()
         .map(|(_, rows)| {
             let (area, perimeter) = rows
                 .iter()
@@ -164,11 +140,14 @@ fn p1(filename: &str) -> usize {
             let perimeter = perimeter - 2 * intersection;
             perimeter * area
         })
-        .sum()
+        .sum();
+
+    Ok(res)
 }
 
-fn p2(filename: &str) -> usize {
-    parse_intervals(filename)
+fn p2(regions: &Regions) -> Result<usize, AocError> {
+    let res = regions
+        .iter()
         .map(|(_, rows)| {
             let (area, sides) = rows
                 .iter()
@@ -182,48 +161,34 @@ fn p2(filename: &str) -> usize {
             let total_sides = sides - diff;
             total_sides * area
         })
-        .sum()
+        .sum();
+
+    Ok(res)
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 12,
+day! { 12: ParsedDay<Regions, usize, usize>,
+    parse: parse_regions,
     part_1: {
         examples: [
-            "example_1.txt",
-            "example_2.txt",
-            "example_3.txt",
+            "example_1.txt" => 140,
+            "example_2.txt" => 772,
+            "example_3.txt" => 1930,
         ],
         func: p1,
     },
     part_2: {
         examples: [
-            "example_1.txt",
-            "example_2.txt",
-            "example_4.txt",
-            "example_5.txt",
+            "example_1.txt" => 80,
+            "example_2.txt" => 436,
+            "example_4.txt" => 236,
+            "example_5.txt" => 368,
         ],
         func: p2,
     }
-};
+}
 
 #[cfg(test)]
 mod d12_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        assert_eq!(SOLUTION.part_1.run_example(0), 140);
-        assert_eq!(SOLUTION.part_1.run_example(1), 772);
-        assert_eq!(SOLUTION.part_1.run_example(2), 1930);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        assert_eq!(SOLUTION.part_2.run_example(0), 80);
-        assert_eq!(SOLUTION.part_2.run_example(1), 436);
-        assert_eq!(SOLUTION.part_2.run_example(2), 236);
-        assert_eq!(SOLUTION.part_2.run_example(3), 368);
-    }
-
     #[test]
     fn playground() {}
 }
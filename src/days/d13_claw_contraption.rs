@@ -1,63 +1,63 @@
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, geometry, parse_number, read_lines},
 };
 
-type Point = (i64, i64);
+type Point = geometry::Point<i64>;
 
-fn parse_file(filename: &str) -> impl Iterator<Item = [Point; 3]> {
-    read_lines(filename)
-        .flat_map(|s| {
-            let (_, xy) = s.split_once(':')?;
-            let (x, y) = xy.split_once(',')?;
+fn parse_file(input: &str) -> Result<Vec<[Point; 3]>, AocError> {
+    let points = read_lines(input)
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, s)| {
+            let (_, xy) = s.split_once(':').ok_or_else(|| AocError::expected("':'").on_line(i))?;
+            let (x, y) = xy.split_once(',').ok_or_else(|| AocError::expected("','").on_line(i))?;
             let (x, y) = (x.trim(), y.trim());
-            Some((x[2..].parse().ok()?, y[2..].parse().ok()?))
+            let x = parse_number(i, s, x.get(2..).unwrap_or(x))?;
+            let y = parse_number(i, s, y.get(2..).unwrap_or(y))?;
+            Ok(Point::new(x, y))
         })
-        .array_chunks()
+        .collect::<Result<Vec<_>, AocError>>()?;
+
+    match points.len() % 3 {
+        0 => Ok(points.into_iter().array_chunks().collect()),
+        _ => Err(AocError::expected("two buttons and a prize for every machine")),
+    }
 }
 
-fn intersect((ax, ay): Point, (bx, by): Point, (px, py): Point) -> Option<Point> {
+fn intersect(a: Point, b: Point, p: Point) -> Option<(i64, i64)> {
     fn solve(v: i64, det: i64) -> Option<i64> {
         let (k, d) = num::integer::div_rem(v, det);
         (k >= 0 && d == 0).then_some(k)
     }
 
-    match ax * by - ay * bx {
+    match a.x * b.y - a.y * b.x {
         0 => None,
         det => {
-            let i = solve(by * px - bx * py, det)?;
-            let j = solve(ax * py - ay * px, det)?;
+            let i = solve(b.y * p.x - b.x * p.y, det)?;
+            let j = solve(a.x * p.y - a.y * p.x, det)?;
             Some((i, j))
         }
     }
 }
 
-fn price(filename: &str, add: i64) -> i64 {
-    parse_file(filename)
-        .flat_map(|[a, b, (px, py)]| intersect(a, b, (px + add, py + add)))
+fn price(input: &str, add: i64) -> Result<i64, AocError> {
+    Ok(parse_file(input)?
+        .into_iter()
+        .flat_map(|[a, b, p]| intersect(a, b, p + Point::new(add, add)))
         .map(|(i, j)| 3 * i + j)
-        .sum()
+        .sum())
 }
 
-fn p1(filename: &str) -> i64 {
-    price(filename, 0)
+fn p1(input: &str) -> Result<i64, AocError> {
+    price(input, 0)
 }
 
-fn p2(filename: &str) -> i64 {
-    price(filename, 10000000000000)
+fn p2(input: &str) -> Result<i64, AocError> {
+    price(input, 10000000000000)
 }
 
-pub const SOLUTION: Day<i64, i64> = day! { 13,
-    part_1: { examples: ["example.txt"], func: p1 },
+day! { 13: Day<i64, i64>,
+    part_1: { examples: ["example.txt" => 480], func: p1 },
     part_2: { examples: [], func: p2 }
-};
-
-#[cfg(test)]
-mod d13_tests {
-    use super::*;
-
-    #[test]
-    fn p1_example_test() {
-        assert_eq!(SOLUTION.part_1.run_example(0), 480);
-    }
 }
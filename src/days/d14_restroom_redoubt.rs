@@ -2,94 +2,110 @@ use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, check_cancelled, geometry, parse_number, read_lines},
 };
 
-type Point = (i32, i32);
-
-fn parse_file(filename: &str) -> ((i32, i32, i32), impl Iterator<Item = (Point, Point)>) {
-    let mut lines = read_lines(filename);
-
-    let whs =
-        lines.next().unwrap().split_whitespace().flat_map(str::parse).collect_tuple().unwrap();
-
-    fn parse_pair(s: &str) -> Option<Point> {
-        let (x, y) = s[2..].split_once(',')?;
-        Some((x.parse().ok()?, y.parse().ok()?))
+type Point = geometry::Point<i32>;
+
+#[allow(clippy::type_complexity)]
+fn parse_file(input: &str) -> Result<((i32, i32, i32), Vec<(Point, Point)>), AocError> {
+    let mut lines = read_lines(input).enumerate();
+
+    let (y, line) = lines.next().ok_or_else(|| AocError::expected("width, height and steps"))?;
+    let whs = line
+        .split_whitespace()
+        .map(|s| parse_number(y, line, s))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .collect_tuple()
+        .ok_or_else(|| AocError::expected("width, height and steps").on_line(y))?;
+
+    fn parse_pair(y: usize, line: &str, s: &str) -> Result<Point, AocError> {
+        let (px, py) = s
+            .get(2..)
+            .and_then(|s| s.split_once(','))
+            .ok_or_else(|| AocError::expected("a pair like 'p=x,y'").on_line(y))?;
+        Ok(Point::new(parse_number(y, line, px)?, parse_number(y, line, py)?))
     }
 
-    let iter = lines.flat_map(|line| {
-        let (p, v) = line.split_once(' ')?;
-        Some((parse_pair(p)?, parse_pair(v)?))
-    });
+    let robots = lines
+        .map(|(y, line)| {
+            let (p, v) =
+                line.split_once(' ').ok_or_else(|| AocError::expected("' '").on_line(y))?;
+            Ok((parse_pair(y, line, p)?, parse_pair(y, line, v)?))
+        })
+        .collect::<Result<_, AocError>>()?;
 
-    (whs, iter)
+    Ok((whs, robots))
 }
 
-fn p1(filename: &str) -> usize {
-    let ((w, h, steps), iter) = parse_file(filename);
+fn p1(input: &str) -> Result<usize, AocError> {
+    let ((w, h, steps), robots) = parse_file(input)?;
 
     let x_mid = (w % 2 == 1).then_some(w / 2);
     let y_mid = (h % 2 == 1).then_some(h / 2);
 
-    iter.flat_map(|((px, py), (vx, vy))| {
-        let x = (vx * steps + px).rem_euclid(w);
-        let y = (vy * steps + py).rem_euclid(h);
-        (Some(x) != x_mid && Some(y) != y_mid).then_some((x, y))
-    })
-    .fold([0, 0, 0, 0], |mut arr, (x, y)| {
-        arr[(((x + w / 2) / w) + 2 * ((y + h / 2) / h)) as usize] += 1;
-        arr
-    })
-    .iter()
-    .product()
+    let res = robots
+        .into_iter()
+        .flat_map(|(p, v)| {
+            let Point { x, y } = (p + v * steps).wrap(w, h);
+            (Some(x) != x_mid && Some(y) != y_mid).then_some((x, y))
+        })
+        .fold([0, 0, 0, 0], |mut arr, (x, y)| {
+            arr[(((x + w / 2) / w) + 2 * ((y + h / 2) / h)) as usize] += 1;
+            arr
+        })
+        .iter()
+        .product();
+
+    Ok(res)
 }
 
-fn p2(filename: &str) -> usize {
-    let ((w, h, _), iter) = parse_file(filename);
-    let robots = iter.collect_vec();
-
-    let (step, points) = (0..)
-        .find_map(|step| {
-            let points = robots
-                .iter()
-                .map(|((px, py), (vx, vy))| {
-                    let x = (vx * step + px).rem_euclid(w) as usize;
-                    let y = (vy * step + py).rem_euclid(h) as usize;
-                    (x, y)
-                })
-                .counts();
-            points.iter().all(|(_, count)| *count == 1).then_some((step, points))
-        })
-        .unwrap();
+fn positions(
+    robots: &[(Point, Point)],
+    (w, h): (i32, i32),
+    step: i32,
+) -> impl Iterator<Item = Point> {
+    robots.iter().map(move |&(p, v)| (p + v * step).wrap(w, h))
+}
 
-    (0..h as usize).for_each(|y| {
-        (0..w as usize).for_each(|x| match points.get(&(x, y)) {
+// The tree shows up the first time no two robots share a tile.
+fn p2(input: &str) -> Result<usize, AocError> {
+    let ((w, h, _), robots) = parse_file(input)?;
+
+    let mut step = 0;
+    loop {
+        check_cancelled()?;
+        if positions(&robots, (w, h), step).all_unique() {
+            return Ok(step as _);
+        }
+        step += 1;
+    }
+}
+
+#[allow(unused)]
+fn print_robots(robots: &[(Point, Point)], (w, h): (i32, i32), step: i32) {
+    let counts = positions(robots, (w, h), step).counts();
+    (0..h).for_each(|y| {
+        (0..w).for_each(|x| match counts.get(&Point::new(x, y)) {
             None => print!("."),
             Some(&c) => print!("{c}"),
         });
         println!();
     });
-
-    step as _
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 14,
-    part_1: { examples: ["example.txt"], func: p1 },
+day! { 14: Day<usize, usize>,
+    part_1: { examples: ["example.txt" => 12], func: p1 },
     part_2: { examples: ["example.txt"], func: p2 }
-};
+}
 
 #[cfg(test)]
 mod d14_tests {
     use super::*;
 
-    #[test]
-    fn p1_example_test() {
-        assert_eq!(SOLUTION.part_1.run_example(0), 12);
-    }
-
     #[test]
     fn playground() {
-        SOLUTION.part_2.run_example(0);
+        SOLUTION.part_2.run_example(0).unwrap();
     }
 }
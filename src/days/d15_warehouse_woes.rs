@@ -1,10 +1,15 @@
-use std::collections::{HashSet, VecDeque};
-
 use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{
+        AocError,
+        Day,
+        geometry::{self, Direction},
+        grid::Grid,
+        read_lines,
+        search::bfs,
+    },
 };
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
@@ -15,193 +20,145 @@ enum Tile {
     Empty,
 }
 
-#[derive(Copy, Clone, Debug)]
-enum Direction {
-    L,
-    U,
-    R,
-    D,
-}
-
-type Point = (usize, usize);
-
-impl Direction {
-    fn make_step(self) -> fn(Point) -> Point {
-        match self {
-            Direction::L => |(x, y)| (x - 1, y),
-            Direction::U => |(x, y)| (x, y - 1),
-            Direction::R => |(x, y)| (x + 1, y),
-            Direction::D => |(x, y)| (x, y + 1),
+type Point = geometry::Point<usize>;
+
+fn parse_map(
+    lines: &mut impl Iterator<Item = (usize, &str)>,
+    widen: bool,
+) -> Result<(Point, Grid<Tile>), AocError> {
+    let rows = std::iter::from_fn(|| lines.next().filter(|(_, line)| !line.is_empty()));
+    let (map, [start]) = Grid::from_lines(rows, ['@'], |c| match c {
+        '.' | '@' => Some(Tile::Empty),
+        'O' => Some(Tile::BoxL),
+        '#' => Some(Tile::Wall),
+        _ => None,
+    })?;
+    let start = start.ok_or_else(|| AocError::expected("a robot ('@')"))?;
+
+    match widen {
+        false => Ok((start, map)),
+        true => {
+            let cells = map
+                .iter()
+                .flat_map(|(_, &t)| match t {
+                    Tile::BoxL => [Tile::BoxL, Tile::BoxR],
+                    t => [t, t],
+                })
+                .collect();
+            Ok((Point::new(2 * start.x, start.y), Grid::new(2 * map.width(), map.height(), cells)))
         }
     }
 }
 
-fn parse_map(lines: &mut impl Iterator<Item = String>, widen: bool) -> (Point, Vec<Vec<Tile>>) {
-    let mut start = (0, 0);
-    let map = std::iter::from_fn(|| lines.next().filter(|line| !line.is_empty()))
-        .enumerate()
-        .map(|(y, line)| {
+fn parse_moves(
+    lines: &mut impl Iterator<Item = (usize, &str)>,
+) -> Result<Vec<Direction>, AocError> {
+    lines
+        .flat_map(|(y, line)| {
             line.chars()
                 .enumerate()
-                .flat_map(|(x, c)| match (c, widen) {
-                    ('.', false) => [Tile::Empty].as_slice().iter(),
-                    ('O', false) => [Tile::BoxL].as_slice().iter(),
-                    ('#', false) => [Tile::Wall].as_slice().iter(),
-                    ('@', false) => {
-                        start = (x, y);
-                        [Tile::Empty].as_slice().iter()
-                    }
-                    ('.', true) => [Tile::Empty, Tile::Empty].as_slice().iter(),
-                    ('O', true) => [Tile::BoxL, Tile::BoxR].as_slice().iter(),
-                    ('#', true) => [Tile::Wall, Tile::Wall].as_slice().iter(),
-                    ('@', true) => {
-                        start = (2 * x, y);
-                        [Tile::Empty, Tile::Empty].as_slice().iter()
-                    }
-                    _ => unreachable!(),
-                })
-                .copied()
-                .collect()
+                .map(|(x, c)| Direction::from_char(c).ok_or(AocError::unexpected_char(y, x, c)))
+                .collect_vec()
         })
-        .collect();
-    (start, map)
+        .collect()
 }
 
-fn parse_moves(lines: &mut impl Iterator<Item = String>) -> impl Iterator<Item = Direction> {
-    lines.flat_map(|line| {
-        line.chars()
-            .map(|c| match c {
-                '<' => Direction::L,
-                '^' => Direction::U,
-                '>' => Direction::R,
-                'v' => Direction::D,
-                _ => unreachable!(),
-            })
-            .collect_vec()
-    })
-}
-
-fn weight_map(map: &[Vec<Tile>]) -> usize {
+fn weight_map(map: &Grid<Tile>) -> usize {
     map.iter()
-        .enumerate()
-        .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, &t)| (x, y, t)))
-        .filter_map(|(x, y, t)| matches!(t, Tile::BoxL).then_some(100 * y + x))
+        .filter_map(|(Point { x, y }, t)| matches!(t, Tile::BoxL).then_some(100 * y + x))
         .sum()
 }
 
-fn p1(filename: &str) -> usize {
-    let mut lines = read_lines(filename);
-    let (mut xy, mut map) = parse_map(&mut lines, false);
+fn p1(input: &str) -> Result<usize, AocError> {
+    let mut lines = read_lines(input).enumerate();
+    let (mut xy, mut map) = parse_map(&mut lines, false)?;
 
-    fn go(start: Point, dir: Direction, map: &[Vec<Tile>]) -> Option<(Point, Point)> {
-        let update_pos = dir.make_step();
-        let first_step = update_pos(start);
-        let (mut x, mut y) = first_step;
+    fn go(start: Point, dir: Direction, map: &Grid<Tile>) -> Option<(Point, Point)> {
+        let first_step = start.step(dir, map.size())?;
+        let mut free = first_step;
 
         loop {
-            match map[y][x] {
+            match map[free] {
                 Tile::Wall => return None,
-                Tile::BoxL => (x, y) = update_pos((x, y)),
-                Tile::Empty => return Some((first_step, (x, y))),
+                Tile::BoxL => free = free.step(dir, map.size())?,
+                Tile::Empty => return Some((first_step, free)),
                 _ => unreachable!(),
             };
         }
     }
 
-    parse_moves(&mut lines).for_each(|m| match go(xy, m, &map) {
-        None => {}
-        Some(((new_x, new_y), (free_x, free_y))) => {
-            map[free_y][free_x] = map[new_y][new_x];
-            map[new_y][new_x] = Tile::Empty;
-            xy = (new_x, new_y);
+    parse_moves(&mut lines)?.into_iter().for_each(|dir| {
+        if let Some((next, free)) = go(xy, dir, &map) {
+            map[free] = map[next];
+            map[next] = Tile::Empty;
+            xy = next;
         }
     });
 
-    weight_map(&map)
+    Ok(weight_map(&map))
 }
 
-fn p2(filename: &str) -> usize {
-    let mut lines = read_lines(filename);
-    let ((mut x, mut y), mut map) = parse_map(&mut lines, true);
-
-    fn go(start: Point, make_step: fn(Point) -> Point, map: &[Vec<Tile>]) -> Option<Vec<Point>> {
-        let mut q = VecDeque::from([start]);
-        let mut boxes = vec![];
-        let mut visited = HashSet::new();
-
-        while let Some(xy) = q.pop_front() {
-            let (x, y) = make_step(xy);
+fn p2(input: &str) -> Result<usize, AocError> {
+    let mut lines = read_lines(input).enumerate();
+    let (mut xy, mut map) = parse_map(&mut lines, true)?;
+
+    // The robot and every box half it pushes, in the order they were reached.
+    fn go(start: Point, dir: Direction, map: &Grid<Tile>) -> Option<Vec<Point>> {
+        let pushed = bfs([start], |&xy| {
+            let next = xy.step(dir, map.size());
+            match next.map(|next| (next, map[next])) {
+                Some((next, Tile::BoxL)) => vec![next, next + Point::new(1, 0)],
+                Some((next, Tile::BoxR)) => vec![next, next - Point::new(1, 0)],
+                _ => vec![],
+            }
+        })
+        .map(|(xy, _)| xy)
+        .collect_vec();
 
-            let vs = match map[y][x] {
-                _ if visited.contains(&(x, y)) => continue,
-                Tile::Wall => return None,
-                Tile::BoxL => [(x, y), (x + 1, y)],
-                Tile::BoxR => [(x, y), (x - 1, y)],
-                Tile::Empty => continue,
-            };
-            visited.extend(vs);
-            boxes.extend(vs);
-            q.extend(vs);
-        }
-        Some(boxes)
+        pushed
+            .iter()
+            .all(|&xy| xy.step(dir, map.size()).is_some_and(|next| map[next] != Tile::Wall))
+            .then_some(pushed)
     }
 
-    parse_moves(&mut lines).map(Direction::make_step).for_each(|make_step| {
-        match go((x, y), make_step, &map) {
-            None => {}
-            Some(mut boxes) => {
-                while let Some((bx, by)) = boxes.pop() {
-                    let tile = map[by][bx];
-                    map[by][bx] = Tile::Empty;
-                    let (bx, by) = make_step((bx, by));
-                    map[by][bx] = tile;
-                }
-                (x, y) = make_step((x, y));
-                map[y][x] = Tile::Empty;
+    parse_moves(&mut lines)?.into_iter().for_each(|dir| {
+        if let Some(mut pushed) = go(xy, dir, &map) {
+            while let Some(from) = pushed.pop() {
+                let to = from.step(dir, map.size()).unwrap();
+                map[to] = map[from];
+                map[from] = Tile::Empty;
             }
+            xy = xy.step(dir, map.size()).unwrap();
         }
     });
 
-    weight_map(&map)
+    Ok(weight_map(&map))
 }
 
 #[allow(unused)]
-fn print_map(map: &[Vec<Tile>], xy: (usize, usize), widen: bool) {
-    map.iter().enumerate().for_each(|(y, row)| {
-        row.iter().enumerate().for_each(|(x, t)| match t {
-            _ if (x, y) == xy => print!("@"),
-            Tile::Wall => print!("#"),
-            Tile::BoxL if widen => print!("["),
-            Tile::BoxL => println!("."),
-            Tile::BoxR => print!("]"),
-            Tile::Empty => print!("."),
-        });
-        println!();
+fn print_map(map: &Grid<Tile>, xy: Point, widen: bool) {
+    let mut chars = map.map(|t| match t {
+        Tile::Wall => '#',
+        Tile::BoxL if widen => '[',
+        Tile::BoxL => 'O',
+        Tile::BoxR => ']',
+        Tile::Empty => '.',
     });
+    chars[xy] = '@';
+    print!("{chars}");
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 15,
-    part_1: { examples: ["example_1.txt", "example_2.txt"], func: p1 },
-    part_2: { examples: ["example_1.txt"], func: p2 }
-};
+day! { 15: Day<usize, usize>,
+    part_1: { examples: ["example_1.txt" => 10092, "example_2.txt" => 2028], func: p1 },
+    part_2: { examples: ["example_1.txt" => 9021], func: p2 }
+}
 
 #[cfg(test)]
 mod d15_tests {
     use super::*;
 
-    #[test]
-    fn p1_example_test() {
-        assert_eq!(SOLUTION.part_1.run_example(0), 10092);
-        assert_eq!(SOLUTION.part_1.run_example(1), 2028);
-    }
-
-    #[test]
-    fn p2_example_test() {
-        assert_eq!(SOLUTION.part_2.run_example(0), 9021);
-    }
-
     #[test]
     fn playground() {
-        SOLUTION.part_2.run_example(0);
+        SOLUTION.part_2.run_example(0).unwrap();
     }
 }
@@ -1,203 +1,77 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
-};
+use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{
+        AocError,
+        ParsedDay,
+        geometry::{self, Direction},
+        grid::Grid,
+        search::{ShortestPaths, dijkstra},
+    },
 };
 
 #[derive(Copy, Clone, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Empty,
     Wall,
 }
 
-type Point = (usize, usize);
+type Point = geometry::Point<usize>;
 
-fn parse_map(filename: &str) -> (Point, Point, Vec<Vec<Tile>>) {
-    let (mut start, mut end) = ((0, 0), (0, 0));
+type Maze = (Point, Point, Grid<Tile>);
 
-    let map = read_lines(filename)
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    '#' => Tile::Wall,
-                    '.' => Tile::Empty,
-                    'S' => {
-                        start = (x, y);
-                        Tile::Empty
-                    }
-                    'E' => {
-                        end = (x, y);
-                        Tile::Empty
-                    }
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
+fn parse_map(input: &str) -> Result<Maze, AocError> {
+    let (map, [start, end]) = Grid::parse_with_markers(input, ['S', 'E'], |c| match c {
+        '#' => Some(Tile::Wall),
+        '.' | 'S' | 'E' => Some(Tile::Empty),
+        _ => None,
+    })?;
 
-    (start, end, map)
+    let start = start.ok_or_else(|| AocError::expected("a start tile ('S')"))?;
+    let end = end.ok_or_else(|| AocError::expected("an end tile ('E')"))?;
+    Ok((start, end, map))
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-enum Direction {
-    W,
-    N,
-    E,
-    S,
-}
+type State = (Point, Direction);
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct Position {
-    cost: i32,
-    xy: Point,
-    dir: Direction,
+fn moves((xy, dir): State, map: &Grid<Tile>) -> impl Iterator<Item = (State, i32)> {
+    let forward = xy.step(dir, map.size()).map(|next| ((next, dir), 1));
+    let turns = [dir.turn_left(), dir.turn_right()].map(|dir| ((xy, dir), 1000));
+    forward.into_iter().chain(turns).filter(|&((xy, _), _)| map[xy] == Tile::Empty)
 }
 
-impl Ord for Position {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
+fn shortest_paths((start, end, map): &Maze) -> (ShortestPaths<State, i32>, [State; 4]) {
+    let paths = dijkstra([(*start, Direction::Right)], |&state| moves(state, map));
+    let ends = Direction::ALL.map(|dir| (*end, dir));
+    (paths, ends)
 }
 
-impl PartialOrd for Position {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+fn p1(maze: &Maze) -> Result<i32, AocError> {
+    let (paths, ends) = shortest_paths(maze);
+    ends.iter().filter_map(|end| paths.cost(end)).min().ok_or_else(AocError::no_answer)
 }
 
-impl Position {
-    fn new(cost: i32, xy: Point, dir: Direction) -> Self {
-        Self { cost, xy, dir }
-    }
-
-    fn moves(&self) -> [Self; 3] {
-        let Self { cost, xy: (x, y), dir } = *self;
+fn p2(maze: &Maze) -> Result<usize, AocError> {
+    let (paths, ends) = shortest_paths(maze);
+    let best = ends.iter().filter_map(|end| paths.cost(end)).min();
+    let ends = ends.into_iter().filter(|end| paths.cost(end) == best);
 
-        match dir {
-            Direction::W => [
-                Self::new(cost + 1, (x - 1, y), Direction::W),
-                Self::new(cost + 1000, (x, y), Direction::S),
-                Self::new(cost + 1000, (x, y), Direction::N),
-            ],
-            Direction::N => [
-                Self::new(cost + 1, (x, y - 1), Direction::N),
-                Self::new(cost + 1000, (x, y), Direction::W),
-                Self::new(cost + 1000, (x, y), Direction::E),
-            ],
-            Direction::E => [
-                Self::new(cost + 1, (x + 1, y), Direction::E),
-                Self::new(cost + 1000, (x, y), Direction::N),
-                Self::new(cost + 1000, (x, y), Direction::S),
-            ],
-            Direction::S => [
-                Self::new(cost + 1, (x, y + 1), Direction::S),
-                Self::new(cost + 1000, (x, y), Direction::E),
-                Self::new(cost + 1000, (x, y), Direction::W),
-            ],
-        }
-    }
+    Ok(paths.on_shortest_paths(ends).into_iter().map(|(xy, _)| xy).unique().count())
 }
 
-fn p1(filename: &str) -> i32 {
-    let (start, end, map) = parse_map(filename);
-    let start = Position::new(0, start, Direction::E);
-
-    let mut q = BinaryHeap::from([start]);
-    let mut visited = HashSet::new();
-
-    while let Some(pos) = q.pop() {
-        if !visited.insert((pos.xy, pos.dir)) {
-            continue;
-        }
-
-        for p in pos.moves() {
-            let (x, y) = p.xy;
-            match map[y][x] {
-                _ if (x, y) == end => return p.cost,
-                Tile::Empty => q.push(p),
-                Tile::Wall => continue,
-            }
-        }
-    }
-    0
-}
-
-fn p2(filename: &str) -> usize {
-    let (start, end, map) = parse_map(filename);
-    let start = Position::new(0, start, Direction::E);
-
-    let mut q = BinaryHeap::from([start]);
-    let mut visited = HashSet::new();
-    let mut backtrack: HashMap<_, Vec<_>> = HashMap::new();
-
-    let mut finishes: Vec<Position> = vec![];
-
-    while let Some(pos) = q.pop() {
-        if !visited.insert((pos.xy, pos.dir)) {
-            continue;
-        }
-
-        if finishes.first().is_some_and(|&f| f.cost < pos.cost) {
-            continue;
-        }
-
-        for p in pos.moves() {
-            let (x, y) = p.xy;
-            match map[y][x] {
-                _ if p.xy == end => {
-                    backtrack.entry(p).or_default().push(pos);
-                    finishes.push(p);
-                }
-                Tile::Empty => {
-                    backtrack.entry(p).or_default().push(pos);
-                    q.push(p);
-                }
-                Tile::Wall => continue,
-            }
-        }
-    }
-
-    let mut tiles = HashSet::new();
-    let mut q = finishes;
-
-    while let Some(pos) = q.pop() {
-        tiles.insert(pos.xy);
-        if let Some(prev) = backtrack.get(&pos) {
-            q.extend(prev);
-        }
-    }
-
-    tiles.len()
+day! { 16: ParsedDay<Maze, i32, usize>,
+    parse: parse_map,
+    part_1: { examples: ["example_1.txt" => 7036, "example_2.txt" => 11048], func: p1 },
+    part_2: { examples: ["example_1.txt" => 45, "example_2.txt" => 64], func: p2 }
 }
-pub const SOLUTION: Day<i32, usize> = day! { 16,
-    part_1: { examples: ["example_1.txt", "example_2.txt"], func: p1 },
-    part_2: { examples: ["example_1.txt", "example_2.txt"], func: p2 }
-};
 
 #[cfg(test)]
 mod d16_tests {
     use super::*;
 
-    #[test]
-    fn p1_example_tests() {
-        assert_eq!(SOLUTION.part_1.run_example(0), 7036);
-        assert_eq!(SOLUTION.part_1.run_example(1), 11048);
-    }
-
-    #[test]
-    fn p2_example_tests() {
-        assert_eq!(SOLUTION.part_2.run_example(0), 45);
-        assert_eq!(SOLUTION.part_2.run_example(1), 64);
-    }
-
     #[test]
     fn playground() {
-        SOLUTION.part_2.run_example(1);
+        SOLUTION.part_2.run_example(1).unwrap();
     }
 }
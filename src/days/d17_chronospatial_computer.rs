@@ -1,119 +1,172 @@
-use itertools::Itertools;
+use itertools::{EitherOrBoth, Itertools};
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, ParsedDay, check_cancelled, parse_number, read_lines},
 };
 
-fn parse_input(filename: &str) -> ([u64; 3], Vec<u64>) {
-    fn parse_register(line: String) -> u64 {
-        let (_, v) = line.split_once(':').unwrap();
-        v.trim().parse().unwrap()
-    }
+type Computer = ([u64; 3], Vec<u64>);
 
-    let mut lines = read_lines(filename);
-    let a = parse_register(lines.next().unwrap());
-    let b = parse_register(lines.next().unwrap());
-    let c = parse_register(lines.next().unwrap());
+fn parse_input(input: &str) -> Result<Computer, AocError> {
+    fn parse_register(y: usize, line: &str) -> Result<u64, AocError> {
+        let (_, v) = line.split_once(':').ok_or_else(|| AocError::expected("':'").on_line(y))?;
+        parse_number(y, line, v)
+    }
 
-    lines.next();
+    let mut lines = read_lines(input).enumerate();
+    let mut next_line =
+        || lines.next().ok_or_else(|| AocError::expected("registers A, B, C and a program"));
+
+    let (y, line) = next_line()?;
+    let a = parse_register(y, line)?;
+    let (y, line) = next_line()?;
+    let b = parse_register(y, line)?;
+    let (y, line) = next_line()?;
+    let c = parse_register(y, line)?;
+
+    next_line()?;
+
+    let (y, line) = next_line()?;
+    let (_, program) = line.split_once(':').ok_or_else(|| AocError::expected("':'").on_line(y))?;
+    let program = program
+        .trim()
+        .split(',')
+        .map(|s| match parse_number(y, line, s)? {
+            v @ 0..8 => Ok(v),
+            _ => Err(AocError::expected("3-bit numbers").on_line(y)),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(([a, b, c], program))
+}
 
-    let program = lines.next().unwrap();
-    let (_, program) = program.split_once(':').unwrap();
-    let program = program.trim().split(',').flat_map(str::parse::<_>).collect();
+fn combo(op: u64, regs: &[u64; 3]) -> Result<u64, AocError> {
+    match op {
+        0..4 => Ok(op),
+        4..7 => Ok(regs[op as usize - 4]),
+        _ => Err(AocError::expected(format!("a combo operand from 0 to 6, got {op}"))),
+    }
+}
 
-    ([a, b, c], program)
+// `a / 2^shift`, which is 0 once the shift reaches the width of a register.
+fn dv(a: u64, shift: u64) -> u64 {
+    u32::try_from(shift).ok().and_then(|shift| a.checked_shr(shift)).unwrap_or(0)
 }
 
-fn combo(op: u64, regs: &[u64; 3]) -> u64 {
-    match op {
-        0..4 => op,
-        4..7 => regs[op as usize - 4],
+fn step(
+    instruction: u64,
+    op: u64,
+    regs: &mut [u64; 3],
+    pointer: &mut usize,
+) -> Result<Option<u64>, AocError> {
+    match instruction {
+        0 => regs[0] = dv(regs[0], combo(op, regs)?), // adv
+        1 => regs[1] ^= op,                           // bxl
+        2 => regs[1] = combo(op, regs)? % 8,          // bst
+        3 if regs[0] == 0 => {}                       // jnz
+        3 => *pointer = op as usize,
+        4 => regs[1] ^= regs[2],                      // bxc
+        5 => return Ok(Some(combo(op, regs)? % 8)),   // out
+        6 => regs[1] = dv(regs[0], combo(op, regs)?), // bdv
+        7 => regs[2] = dv(regs[0], combo(op, regs)?), // cdv
         _ => unreachable!(),
     }
+    Ok(None)
 }
 
-fn execute(regs: &mut [u64; 3], program: &[u64]) -> impl Iterator<Item = u64> {
+// A program may loop forever without output, so every step checks for cancellation.
+fn execute(regs: &mut [u64; 3], program: &[u64]) -> impl Iterator<Item = Result<u64, AocError>> {
     let mut pointer = 0;
 
     std::iter::from_fn(move || {
         let instruction = *program.get(pointer)?;
         let op = *program.get(pointer + 1)?;
         pointer += 2;
-
-        match instruction {
-            0 => regs[0] /= 2u64.pow(combo(op, regs) as u32), // adv
-            1 => regs[1] ^= op,                               // bxl
-            2 => regs[1] = combo(op, regs) % 8,               // bst
-            3 if regs[0] == 0 => {}                           // jnz
-            3 => {
-                pointer = op as usize;
-                return Some(None);
-            }
-            4 => regs[1] ^= regs[2],                                   // bxc
-            5 => return Some(Some((combo(op, regs) % 8, *regs))),      // out
-            6 => regs[1] = regs[0] / 2u64.pow(combo(op, regs) as u32), // bdv
-            7 => regs[2] = regs[0] / 2u64.pow(combo(op, regs) as u32), // cdv
-            _ => unreachable!(),
-        }
-        Some(None)
+        Some(check_cancelled().and_then(|_| step(instruction, op, regs, &mut pointer)))
     })
-    .flatten()
-    .map(|(v, _)| v)
+    .filter_map(Result::transpose)
 }
 
-fn p1(filename: &str) -> String {
-    let (mut regs, program) = parse_input(filename);
+fn p1((regs, program): &Computer) -> Result<String, AocError> {
+    let output: Vec<_> = execute(&mut regs.clone(), program).try_collect()?;
+    Ok(output.iter().join(","))
+}
 
-    execute(&mut regs, &program).join(",")
+fn p2((_, program): &Computer) -> Result<u64, AocError> {
+    let start_reg = 8_u64
+        .checked_pow(program.len() as u32)
+        .ok_or_else(|| AocError::expected("a program of at most 21 numbers"))?;
+    traverse(start_reg - 1, program, 0)?.ok_or_else(AocError::no_answer)
 }
 
-fn p2(filename: &str) -> u64 {
-    let (_, program) = parse_input(filename);
-    let start_reg = 8_u64.pow(program.len() as u32) - 1;
-    traverse(start_reg, &program, 0).unwrap()
+// Whether starting from `reg` outputs exactly the program, ignoring the first `skip` numbers.
+fn outputs_program(reg: u64, program: &[u64], skip: usize) -> Result<bool, AocError> {
+    let mut regs = [reg, 0, 0];
+    let output = Itertools::zip_longest(execute(&mut regs, program), program);
+    for (i, pair) in output.enumerate() {
+        let EitherOrBoth::Both(out, &expected) = pair else { return Ok(false) };
+        if out? != expected && i >= skip {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
 
-fn traverse(reg: u64, program: &[u64], d: usize) -> Option<u64> {
+fn traverse(reg: u64, program: &[u64], d: usize) -> Result<Option<u64>, AocError> {
+    check_cancelled()?;
     if d == program.len() {
-        return Some(reg);
+        return Ok(Some(reg));
     }
     let d0 = program.len() - d - 1;
-    (0..8).find_map(|x| {
+    for x in 0..8 {
         let p = 8_u64.pow(d0 as u32);
         let reg = reg - (reg / p % 8) * p + x * p;
-        Itertools::zip_longest(execute(&mut [reg, 0, 0], program), program.iter())
-            .skip(d0)
-            .all(|pair| pair.both().is_some_and(|(x, &y)| x == y))
-            .then(|| traverse(reg, program, d + 1))
-            .flatten()
-    })
+        if outputs_program(reg, program, d0)?
+            && let Some(res) = traverse(reg, program, d + 1)?
+        {
+            return Ok(Some(res));
+        }
+    }
+    Ok(None)
 }
 
-pub const SOLUTION: Day<String, u64> = day! { 17,
-    part_1: { examples: ["example_1.txt", "example_2.txt"], func: p1 },
-    part_2: { examples: ["example_2.txt"], func: p2 }
-};
+day! { 17: ParsedDay<Computer, String, u64>,
+    parse: parse_input,
+    part_1: { examples: ["example_1.txt" => "4,6,3,5,6,3,5,2,1,0", "example_2.txt"], func: p1 },
+    part_2: { examples: ["example_2.txt" => 117440], func: p2 }
+}
 
 #[cfg(test)]
 mod d17_tests {
     use super::*;
+    use crate::utils::{CancelToken, input_path, read_input, with_cancel_token};
 
     #[test]
-    fn p1_example_tests() {
-        assert_eq!(&SOLUTION.part_1.run_example(0), "4,6,3,5,6,3,5,2,1,0");
+    fn invalid_programs() {
+        let run = |c: u64, program: &str| {
+            let registers = format!("Register A: 1\nRegister B: 0\nRegister C: {c}\n");
+            let input = format!("{registers}\nProgram: {program}");
+            p1(&parse_input(&input).unwrap())
+        };
+        let err = run(0, "0,7,5,4").unwrap_err();
+        assert_eq!(err.to_string(), "expected a combo operand from 0 to 6, got 7");
+        assert_eq!(run(99, "0,6,5,4").unwrap(), "0");
+        assert_eq!(dv(u64::MAX, 64), 0);
     }
 
     #[test]
-    fn p2_example_tests() {
-        // task:   88645131227352 too low
-        //       4774437135335516
-        assert_eq!(SOLUTION.part_2.run_example(0), 117440);
+    fn endless_programs_can_be_cancelled() {
+        let token = CancelToken::new();
+        token.cancel();
+        let computer = ([1, 0, 0], vec![3, 0]);
+        let res = with_cancel_token(token, || p1(&computer));
+        assert_eq!(res.unwrap_err().to_string(), "cancelled");
     }
 
     #[test]
     fn playground() {
-        let (_, program) = parse_input(SOLUTION.part_1.task);
+        let input = read_input(input_path(SOLUTION.part_1.task)).unwrap();
+        let (_, program) = parse_input(&input).unwrap();
         // let (_, program) = parse_input(SOLUTION.part_1.examples[1]);
 
         let start_reg = 8_u64.pow(program.len() as u32) - 1;
@@ -1,87 +1,79 @@
-use std::collections::{HashSet, VecDeque};
-
 use itertools::Itertools;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, geometry::Point, grid::Grid, parse_number, read_lines, search::astar},
 };
 
-fn parse_input(filename: &str) -> (usize, usize, usize, impl Iterator<Item = (usize, usize)>) {
-    let mut lines = read_lines(filename);
-    let (h, w, steps) =
-        lines.next().unwrap().split(',').flat_map(str::parse).collect_tuple().unwrap();
-
-    let pairs = lines.filter(|line| !line.is_empty()).flat_map(|line| {
-        let (a, b) = line.split_once(',')?;
-        Some((a.parse::<usize>().ok()?, b.parse::<usize>().ok()?))
-    });
-
-    (w, h, steps, pairs)
-}
-
-fn route(w: usize, h: usize, blocks: HashSet<(usize, usize)>) -> Option<usize> {
-    let mut visited = HashSet::from([(0, 0)]);
-    let mut q = VecDeque::from([(0, 0, 0)]);
-
-    while let Some((x, y, step)) = q.pop_front() {
-        if (x, y) == (w - 1, h - 1) {
-            return Some(step);
-        }
+#[allow(clippy::type_complexity)]
+fn parse_input(input: &str) -> Result<(usize, usize, usize, Vec<(usize, usize)>), AocError> {
+    let mut lines = read_lines(input).enumerate();
 
-        let next_steps = [
-            (x > 0).then(|| (x - 1, y, step + 1)),
-            (y > 0).then(|| (x, y - 1, step + 1)),
-            (x + 1 < w).then_some((x + 1, y, step + 1)),
-            (y + 1 < h).then_some((x, y + 1, step + 1)),
-        ]
+    let (y, line) = lines.next().ok_or_else(|| AocError::expected("height, width and steps"))?;
+    let (h, w, steps) = line
+        .split(',')
+        .map(|s| parse_number(y, line, s))
+        .collect::<Result<Vec<_>, _>>()?
         .into_iter()
-        .flatten()
-        .filter(|&(x, y, _)| !blocks.contains(&(x, y)) && visited.insert((x, y)));
-
-        q.extend(next_steps);
+        .collect_tuple()
+        .filter(|&(h, w, _)| h > 0 && w > 0)
+        .ok_or_else(|| AocError::expected("height, width and steps").on_line(y))?;
+
+    if let Some((y, line)) = lines.next()
+        && !line.is_empty()
+    {
+        return Err(AocError::expected("a blank line after the header").on_line(y));
     }
 
-    None
+    let pairs = lines
+        .map(|(y, line)| {
+            let (a, b) =
+                line.split_once(',').ok_or_else(|| AocError::expected("','").on_line(y))?;
+            match (parse_number(y, line, a)?, parse_number(y, line, b)?) {
+                (bx, by) if bx < w && by < h => Ok((bx, by)),
+                _ => Err(AocError::expected(format!("a position inside {w}x{h}")).on_line(y)),
+            }
+        })
+        .collect::<Result<_, AocError>>()?;
+
+    Ok((w, h, steps, pairs))
 }
 
-fn p1(filename: &str) -> usize {
-    let (w, h, steps, blocks) = parse_input(filename);
-    route(w, h, blocks.take(steps).collect()).unwrap()
+fn route(w: usize, h: usize, blocks: &[(usize, usize)]) -> Option<usize> {
+    let mut blocked = Grid::filled(w, h, false);
+    blocks.iter().for_each(|&xy| blocked[Point::from(xy)] = true);
+
+    let exit = Point::new(w - 1, h - 1);
+    let (steps, _) = astar(
+        Point::new(0, 0),
+        |&xy| xy.neighbours((w, h)).filter(|&next| !blocked[next]).map(|next| (next, 1)),
+        |&xy| xy.manhattan(exit),
+        |&xy| xy == exit,
+    )?;
+    Some(steps)
 }
 
-fn p2(filename: &str) -> (usize, usize) {
-    let (w, h, _, blocks) = parse_input(filename);
-    let blocks: Vec<_> = blocks.collect();
+fn p1(input: &str) -> Result<usize, AocError> {
+    let (w, h, steps, blocks) = parse_input(input)?;
+    route(w, h, &blocks[..steps.min(blocks.len())]).ok_or_else(AocError::no_answer)
+}
+
+fn p2(input: &str) -> Result<(usize, usize), AocError> {
+    let (w, h, _, blocks) = parse_input(input)?;
 
     let (mut l, mut r) = (0, blocks.len());
     while r > l {
         let m = (r + l) / 2;
-        match route(w, h, blocks[..=m].iter().copied().collect()) {
+        match route(w, h, &blocks[..=m]) {
             Some(_) => l = m + 1,
             None => r = m,
         }
     }
 
-    blocks[r]
+    blocks.get(r).copied().ok_or_else(AocError::no_answer)
 }
 
-pub const SOLUTION: Day<usize, (usize, usize)> = day! { 18,
-    part_1: { examples: ["example.txt"], func: p1 },
-    part_2: { examples: ["example.txt"], func: p2 }
-};
-
-#[cfg(test)]
-mod d18_tests {
-    use super::*;
-
-    #[test]
-    fn p1_examples_test() {
-        assert_eq!(SOLUTION.part_1.run_example(0), 22);
-    }
-
-    #[test]
-    fn p2_examples_test() {
-        assert_eq!(SOLUTION.part_2.run_example(0), (6, 1));
-    }
+day! { 18: Day<usize, (usize, usize)>,
+    part_1: { examples: ["example.txt" => 22], func: p1 },
+    part_2: { examples: ["example.txt" => (6, 1)], func: p2 }
 }
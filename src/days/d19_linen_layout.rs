@@ -2,17 +2,44 @@ use std::{collections::HashSet, str::pattern::Pattern};
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, read_lines},
 };
 
-fn parse_input(filename: &str) -> (Vec<String>, Vec<String>) {
-    let mut lines = read_lines(filename);
+// White, blue, black, red and green.
+const COLOURS: &str = "wubrg";
 
-    let towels = lines.next().unwrap().split(", ").map(String::from).collect();
-    lines.next();
-    let patterns = lines.collect();
+// Checks that `line` only has stripe colours, besides the `separators` between towels.
+fn check_colours(y: usize, line: &str, separators: &str) -> Result<(), AocError> {
+    match line.char_indices().find(|&(_, c)| !COLOURS.contains(c) && !separators.contains(c)) {
+        Some((x, c)) => Err(AocError::unexpected_char(y, x, c)),
+        None => Ok(()),
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Vec<&str>, Vec<&str>), AocError> {
+    let mut lines = read_lines(input).enumerate();
+
+    let (y, towels) = lines.next().ok_or_else(|| AocError::expected("a list of towels"))?;
+    check_colours(y, towels, ", ")?;
+    let towels: Vec<_> = towels.split(", ").collect();
+    if towels.iter().any(|towel| towel.is_empty() || towel.contains([',', ' '])) {
+        return Err(AocError::expected("a list of towels like 'r, wr, b'").on_line(y));
+    }
+
+    if let Some((y, line)) = lines.next()
+        && !line.is_empty()
+    {
+        return Err(AocError::expected("a blank line after the towels").on_line(y));
+    }
+
+    let patterns = lines
+        .map(|(y, line)| match line.is_empty() {
+            true => Err(AocError::expected("a pattern").on_line(y)),
+            false => check_colours(y, line, "").map(|_| line),
+        })
+        .collect::<Result<_, _>>()?;
 
-    (towels, patterns)
+    Ok((towels, patterns))
 }
 
 fn ways_to_combine(towels: &HashSet<&str>, pattern: &str) -> usize {
@@ -27,39 +54,43 @@ fn ways_to_combine(towels: &HashSet<&str>, pattern: &str) -> usize {
     )[pattern.len() - 1]
 }
 
-fn p1(filename: &str) -> usize {
-    let (towels, patterns) = parse_input(filename);
-    let towels: HashSet<_> = towels.iter().map(String::as_str).collect();
+fn p1(input: &str) -> Result<usize, AocError> {
+    let (towels, patterns) = parse_input(input)?;
+    let towels: HashSet<_> = towels.into_iter().collect();
 
-    patterns.iter().filter(|p| ways_to_combine(&towels, p.as_str()) > 0).count()
+    Ok(patterns.iter().filter(|p| ways_to_combine(&towels, p) > 0).count())
 }
 
-fn p2(filename: &str) -> usize {
-    let (towels, patterns) = parse_input(filename);
-    let towels: HashSet<_> = towels.iter().map(String::as_str).collect();
+fn p2(input: &str) -> Result<usize, AocError> {
+    let (towels, patterns) = parse_input(input)?;
+    let towels: HashSet<_> = towels.into_iter().collect();
 
-    patterns.iter().map(|p| ways_to_combine(&towels, p.as_str())).sum()
+    Ok(patterns.iter().map(|p| ways_to_combine(&towels, p)).sum())
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 19,
-    part_1: { examples: ["example.txt"], func: p1 },
-    part_2: { examples: ["example.txt"], func: p2 }
-};
+day! { 19: Day<usize, usize>,
+    part_1: { examples: ["example.txt" => 6], func: p1 },
+    part_2: { examples: ["example.txt" => 16], func: p2 }
+}
 
 #[cfg(test)]
 mod d19_tests {
     use super::*;
 
     #[test]
-    fn p1_examples_test() {
-        assert_eq!(SOLUTION.part_1.run_example(0), 6);
-    }
+    fn playground() {}
 
     #[test]
-    fn p2_examples_test() {
-        assert_eq!(SOLUTION.part_2.run_example(0), 16);
+    fn rejects_bad_layouts() {
+        assert_eq!(p2("r, wr\n\nrwr\n\n").unwrap(), 1);
+        assert_eq!(p2("r, wr\n\n").unwrap(), 0);
+        assert!(parse_input("r, wr\nrwr\n").is_err());
+        assert!(parse_input("r, wr\n\nrwr\n\nwr\n").is_err());
+        assert!(parse_input("r, , wr\n\nrwr\n").is_err());
+        assert!(parse_input("r,wr\n\nrwr\n").is_err());
+        assert_eq!(
+            parse_input("r, wr\n\nrwé\n").err().unwrap().to_string(),
+            "3:3: unexpected character 'é'"
+        );
     }
-
-    #[test]
-    fn playground() {}
 }
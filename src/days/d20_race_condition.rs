@@ -1,8 +1,8 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
 use crate::{
     day,
-    utils::{Day, Task, read_lines},
+    utils::{AocError, Day, check_cancelled, geometry, grid::Grid, search::bfs},
 };
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -11,144 +11,74 @@ enum Tile {
     Wall,
 }
 
-type Point = (usize, usize);
-
-fn parse_map(filename: &str) -> (Point, Point, Vec<Vec<Tile>>) {
-    let (mut start, mut end) = ((0, 0), (0, 0));
-
-    let map = read_lines(filename)
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    '#' => Tile::Wall,
-                    '.' => Tile::Empty,
-                    'S' => {
-                        start = (x, y);
-                        Tile::Empty
-                    }
-                    'E' => {
-                        end = (x, y);
-                        Tile::Empty
-                    }
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
-
-    (start, end, map)
+type Point = geometry::Point<usize>;
+
+fn parse_map(input: &str) -> Result<(Point, Point, Grid<Tile>), AocError> {
+    let (map, [start, end]) = Grid::parse_with_markers(input, ['S', 'E'], |c| match c {
+        '#' => Some(Tile::Wall),
+        '.' | 'S' | 'E' => Some(Tile::Empty),
+        _ => None,
+    })?;
+
+    let start = start.ok_or_else(|| AocError::expected("a start tile ('S')"))?;
+    let end = end.ok_or_else(|| AocError::expected("an end tile ('E')"))?;
+    Ok((start, end, map))
 }
 
-fn simulate(filename: &str, cheat_len: i32, threshold: i32) -> usize {
-    let (start, end, map) = parse_map(filename);
-    let (w, h) = (map[0].len(), map.len());
+fn simulate(input: &str, cheat_len: i32, threshold: i32) -> Result<usize, AocError> {
+    let (start, end, map) = parse_map(input)?;
 
     let distances = find_distances(end, &map);
+    let track = bfs([start], |&xy| xy.neighbours(map.size()).filter(|p| distances.contains_key(p)));
 
-    let mut q = VecDeque::from([start]);
-    let mut visited = HashSet::new();
     let mut cheats = HashMap::new();
+    for (xy0, _) in track {
+        check_cancelled()?;
+        let Some(&d) = distances.get(&xy0) else { continue };
 
-    while let Some(xy0) = q.pop_front() {
-        if !visited.insert(xy0) {
-            continue;
-        }
-
-        match distances.get(&xy0) {
-            None => {}
-            Some(&d) => {
-                q.extend(moves(xy0, w, h).into_iter().flatten());
-
-                find_exits(xy0, &map, cheat_len).iter().for_each(|&(cheat_d, cheat_end)| {
-                    let cheat_d = distances.get(&cheat_end).unwrap() + cheat_d;
-                    cheats.insert((xy0, cheat_end), d - cheat_d);
-                });
+        find_exits(xy0, &map, cheat_len).iter().for_each(|&(cheat_d, cheat_end)| {
+            if let Some(&rest) = distances.get(&cheat_end) {
+                cheats.insert((xy0, cheat_end), d - (rest + cheat_d));
             }
-        }
+        });
     }
 
-    cheats.iter().filter(|&(_, &d)| d >= threshold).count()
+    Ok(cheats.iter().filter(|&(_, &d)| d >= threshold).count())
 }
 
-fn moves((x, y): Point, w: usize, h: usize) -> [Option<(usize, usize)>; 4] {
-    [
-        (x > 0).then(|| (x - 1, y)),
-        (y > 0).then(|| (x, y - 1)),
-        (x + 1 < w).then_some((x + 1, y)),
-        (y + 1 < h).then_some((x, y + 1)),
-    ]
-}
-
-fn find_distances(end: Point, map: &[Vec<Tile>]) -> HashMap<Point, i32> {
-    let (w, h) = (map[0].len(), map.len());
-    let mut q = VecDeque::from([(end, 0)]);
-    let mut distances = HashMap::from([(end, 0)]);
-
-    while let Some((xy, steps)) = q.pop_front() {
-        moves(xy, w, h).into_iter().flatten().for_each(|(x, y)| match map[y][x] {
-            Tile::Empty if !distances.contains_key(&(x, y)) => {
-                distances.insert((x, y), steps + 1);
-                q.push_back(((x, y), steps + 1));
-            }
-            _ => {}
-        });
-    }
-
-    distances
+fn find_distances(end: Point, map: &Grid<Tile>) -> HashMap<Point, i32> {
+    bfs([end], |&xy| xy.neighbours(map.size()).filter(|&next| map[next] == Tile::Empty))
+        .map(|(xy, steps)| (xy, steps as i32))
+        .collect()
 }
 
-fn find_exits((x0, y0): Point, map: &[Vec<Tile>], cheat_len: i32) -> Vec<(i32, Point)> {
-    let (w, h) = (map[0].len(), map.len());
-
-    let up = y0.saturating_sub(cheat_len as usize);
-    let down = (y0 + cheat_len as usize).min(h - 1);
-
-    (up..=down)
-        .flat_map(|y| {
-            let dx = cheat_len as usize - y0.abs_diff(y);
-            let left = x0.saturating_sub(dx);
-            let right = (x0 + dx).min(w - 1);
-            (left..=right).map(move |x| (x, y))
-        })
-        .filter(|&(x, y)| matches!(map[y][x], Tile::Empty))
-        .map(|(x, y)| ((x0.abs_diff(x) + y0.abs_diff(y)) as i32, (x, y)))
+fn find_exits(xy0: Point, map: &Grid<Tile>, cheat_len: i32) -> Vec<(i32, Point)> {
+    xy0.within(cheat_len as usize, map.size())
+        .filter(|&xy| map[xy] == Tile::Empty)
+        .map(|xy| (xy0.manhattan(xy) as i32, xy))
         .collect()
 }
 
-fn p1(filename: &str) -> usize {
-    simulate(filename, 2, 100)
+fn p1(input: &str) -> Result<usize, AocError> {
+    simulate(input, 2, 100)
 }
 
-fn p2(filename: &str) -> usize {
-    simulate(filename, 20, 100)
+fn p2(input: &str) -> Result<usize, AocError> {
+    simulate(input, 20, 100)
 }
 
-pub const SOLUTION: Day<usize, usize> = day! { 20,
+day! { 20: Day<usize, usize>,
     part_1: { examples: ["example.txt"], func: p1 },
-    part_2: { examples: ["example.txt"], func: p2 }
-};
+    part_2: { examples: ["example.txt" => 0], func: p2 }
+}
 
 #[cfg(test)]
 mod d20_tests {
     use super::*;
 
-    #[test]
-    fn p1_examples_test() {
-        // 1310 too low
-        // 1327 too low
-        // assert_eq!(SOLUTION.part_1.run_example(0), 0);
-    }
-
-    #[test]
-    fn p2_examples_test() {
-        assert_eq!(SOLUTION.part_2.run_example(0), 0);
-    }
-
     #[test]
     fn playground() {
-        let res = SOLUTION.part_1.run_example(0);
+        let res = SOLUTION.part_1.run_example(0).unwrap();
         println!("{res}");
     }
 }
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::{
+    day,
+    utils::{
+        AocError,
+        Day,
+        geometry::{Direction, Point},
+        parse_number,
+        read_lines,
+    },
+};
+
+const NUMERIC: [&str; 4] = ["789", "456", "123", " 0A"];
+const DIRECTIONAL: [&str; 2] = [" ^A", "<v>"];
+
+fn position(keypad: &[&str], key: char) -> Option<Point<usize>> {
+    keypad.iter().enumerate().find_map(|(y, row)| Some(Point::new(row.find(key)?, y)))
+}
+
+// Moving along one axis and then the other, in both orders, as long as the arm never points
+// at the gap. Anything that zigzags only costs more presses further up the chain.
+fn paths(keypad: &[&str], from: char, to: char) -> Vec<String> {
+    let [from, to, gap] = [from, to, ' '].map(|key| position(keypad, key).unwrap());
+
+    let horizontal = match to.x < from.x {
+        true => Direction::Left,
+        false => Direction::Right,
+    };
+    let vertical = match to.y < from.y {
+        true => Direction::Up,
+        false => Direction::Down,
+    };
+    let horizontal = horizontal.to_char().to_string().repeat(from.x.abs_diff(to.x));
+    let vertical = vertical.to_char().to_string().repeat(from.y.abs_diff(to.y));
+
+    let mut paths = vec![];
+    if Point::new(to.x, from.y) != gap {
+        paths.push(format!("{horizontal}{vertical}A"));
+    }
+    if Point::new(from.x, to.y) != gap {
+        paths.push(format!("{vertical}{horizontal}A"));
+    }
+    paths.dedup();
+    paths
+}
+
+// Presses needed from the human to get `robots` layers of directional keypads to type a sequence.
+struct Chain {
+    robots: usize,
+    memo: HashMap<(char, char, usize), usize>,
+}
+
+impl Chain {
+    fn new(robots: usize) -> Self {
+        Self { robots, memo: HashMap::new() }
+    }
+
+    // Every arm starts on, and returns to, `A` between the keys of the layer below.
+    fn sequence_cost(&mut self, keys: &str, depth: usize) -> usize {
+        match depth {
+            0 => keys.len(),
+            _ => std::iter::once('A')
+                .chain(keys.chars())
+                .tuple_windows()
+                .map(|(from, to)| self.press_cost(from, to, depth))
+                .sum(),
+        }
+    }
+
+    fn press_cost(&mut self, from: char, to: char, depth: usize) -> usize {
+        if let Some(&cost) = self.memo.get(&(from, to, depth)) {
+            return cost;
+        }
+
+        let cost = paths(&DIRECTIONAL, from, to)
+            .iter()
+            .map(|path| self.sequence_cost(path, depth - 1))
+            .min()
+            .unwrap();
+        self.memo.insert((from, to, depth), cost);
+        cost
+    }
+
+    fn code_cost(&mut self, code: &str) -> usize {
+        std::iter::once('A')
+            .chain(code.chars())
+            .tuple_windows()
+            .map(|(from, to)| {
+                paths(&NUMERIC, from, to)
+                    .iter()
+                    .map(|path| self.sequence_cost(path, self.robots))
+                    .min()
+                    .unwrap()
+            })
+            .sum()
+    }
+}
+
+fn parse_codes(input: &str) -> Result<Vec<(&str, usize)>, AocError> {
+    let codes: Vec<_> = read_lines(input)
+        .enumerate()
+        .map(|(y, line)| {
+            let key = |c| c != ' ' && position(&NUMERIC, c).is_some();
+            if let Some((x, c)) = line.char_indices().find(|&(_, c)| !key(c)) {
+                return Err(AocError::unexpected_char(y, x, c));
+            }
+            let digits = line
+                .strip_suffix('A')
+                .ok_or_else(|| AocError::expected("a code ending in 'A'").on_line(y))?;
+            Ok((line, parse_number(y, line, digits)?))
+        })
+        .try_collect()?;
+    match codes.is_empty() {
+        true => Err(AocError::expected("at least one door code")),
+        false => Ok(codes),
+    }
+}
+
+fn complexity(input: &str, robots: usize) -> Result<usize, AocError> {
+    let mut chain = Chain::new(robots);
+    Ok(parse_codes(input)?.into_iter().map(|(code, n)| chain.code_cost(code) * n).sum())
+}
+
+fn p1(input: &str) -> Result<usize, AocError> {
+    complexity(input, 2)
+}
+
+fn p2(input: &str) -> Result<usize, AocError> {
+    complexity(input, 25)
+}
+
+day! { 21: Day<usize, usize>,
+    part_1: { examples: ["example.txt" => 126384], func: p1 },
+    part_2: { examples: ["example.txt" => 154115708116294_usize], func: p2 }
+}
+
+#[cfg(test)]
+mod d21_tests {
+    use super::*;
+
+    #[test]
+    fn sequence_lengths() {
+        let mut chain = Chain::new(2);
+        let lengths = ["029A", "980A", "179A", "456A", "379A"].map(|code| chain.code_cost(code));
+        assert_eq!(lengths, [68, 60, 68, 64, 64]);
+        assert_eq!(Chain::new(0).code_cost("029A"), "<A^A>^^AvvvA".len());
+    }
+
+    #[test]
+    fn paths_avoid_the_gap() {
+        assert_eq!(paths(&NUMERIC, 'A', '1'), ["^<<A"]);
+        assert_eq!(paths(&DIRECTIONAL, '<', 'A'), [">>^A"]);
+        assert_eq!(paths(&DIRECTIONAL, 'A', 'A'), ["A"]);
+    }
+
+    #[test]
+    fn rejects_bad_codes() {
+        assert!(parse_codes("029B\n").is_err());
+        assert!(parse_codes("\n").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use itertools::Itertools;
+
+use crate::{
+    day,
+    utils::{AocError, Day, parse_number, read_lines},
+};
+
+const STEPS: usize = 2000;
+// Price changes lie in -9..=9, so a window of four packs into a base-19 index.
+const WINDOWS: usize = 19 * 19 * 19 * 19;
+
+fn parse_secrets(input: &str) -> Result<Vec<u64>, AocError> {
+    let secrets: Vec<_> =
+        read_lines(input).enumerate().map(|(y, line)| parse_number(y, line, line)).try_collect()?;
+    match secrets.is_empty() {
+        true => Err(AocError::expected("at least one initial secret")),
+        false => Ok(secrets),
+    }
+}
+
+fn next(secret: u64) -> u64 {
+    let prune = |s: u64| s & 0xFF_FFFF;
+    let secret = prune(secret ^ (secret << 6));
+    let secret = prune(secret ^ (secret >> 5));
+    prune(secret ^ (secret << 11))
+}
+
+fn evolve(secret: u64) -> impl Iterator<Item = u64> {
+    std::iter::successors(Some(secret), |&s| Some(next(s))).take(STEPS + 1)
+}
+
+fn p1(input: &str) -> Result<u64, AocError> {
+    Ok(parse_secrets(input)?.into_iter().map(|s| evolve(s).last().unwrap()).sum())
+}
+
+// Bananas bought across all buyers by each window of four price changes.
+fn bananas(secrets: &[u64]) -> Vec<u32> {
+    let mut bananas = vec![0u32; WINDOWS];
+    // The last buyer (1-based) that sold on each window, so only their first sale counts.
+    let mut seen = vec![0u32; WINDOWS];
+
+    for (buyer, &secret) in (1..).zip(secrets) {
+        let mut window = 0;
+        let mut prev = secret % 10;
+
+        for (i, price) in evolve(secret).skip(1).map(|s| s % 10).enumerate() {
+            window = (window * 19 + (price + 9 - prev) as usize) % WINDOWS;
+            prev = price;
+
+            if i >= 3 && seen[window] != buyer {
+                seen[window] = buyer;
+                bananas[window] += price as u32;
+            }
+        }
+    }
+
+    bananas
+}
+
+fn p2(input: &str) -> Result<u32, AocError> {
+    Ok(bananas(&parse_secrets(input)?).into_iter().max().unwrap_or(0))
+}
+
+day! { 22: Day<u64, u32>,
+    part_1: { examples: ["example_1.txt" => 37327623], func: p1 },
+    part_2: { examples: ["example_2.txt" => 23], func: p2 }
+}
+
+#[cfg(test)]
+mod d22_tests {
+    use super::*;
+
+    #[test]
+    fn secret_sequence() {
+        let secrets: Vec<_> = evolve(123).take(4).collect();
+        assert_eq!(secrets, [123, 15887950, 16495136, 527345]);
+        assert_eq!(evolve(1).last(), Some(8685429));
+    }
+
+    #[test]
+    fn best_window() {
+        let window = [-2, 1, -1, 3].iter().fold(0, |w, &d: &i32| w * 19 + (d + 9) as usize);
+        assert_eq!(bananas(&[1, 2, 3, 2024])[window], 23);
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(parse_secrets("\n").is_err());
+    }
+}
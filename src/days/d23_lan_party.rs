@@ -0,0 +1,51 @@
+use itertools::Itertools;
+
+use crate::{
+    day,
+    utils::{AocError, Day, graph::Graph, read_lines},
+};
+
+fn parse_network(input: &str) -> Result<Graph<&str>, AocError> {
+    let network: Graph<_> = read_lines(input)
+        .enumerate()
+        .map(|(y, line)| {
+            let (a, b) = line
+                .split_once('-')
+                .filter(|(a, b)| !a.is_empty() && !b.is_empty())
+                .ok_or_else(|| AocError::expected("a connection like 'aa-bb'").on_line(y))?;
+            Ok((a, b))
+        })
+        .collect::<Result<_, AocError>>()?;
+    match network.is_empty() {
+        true => Err(AocError::expected("at least one connection")),
+        false => Ok(network),
+    }
+}
+
+fn p1(input: &str) -> Result<usize, AocError> {
+    let network = parse_network(input)?;
+    let chief = |&i: &usize| network.node(i).starts_with('t');
+    Ok(network.triangles().filter(|triangle| triangle.iter().any(chief)).count())
+}
+
+fn p2(input: &str) -> Result<String, AocError> {
+    let network = parse_network(input)?;
+    Ok(network.max_clique().into_iter().map(|i| network.node(i)).sorted().join(","))
+}
+
+day! { 23: Day<usize, String>,
+    part_1: { examples: ["example.txt" => 7], func: p1 },
+    part_2: { examples: ["example.txt" => "co,de,ka,ta"], func: p2 }
+}
+
+#[cfg(test)]
+mod d23_tests {
+    use super::*;
+
+    #[test]
+    fn rejects_bad_connections() {
+        assert!(parse_network("kh-tc\nkh\n").is_err());
+        assert!(parse_network("kh-\n").is_err());
+        assert!(parse_network("").is_err());
+    }
+}
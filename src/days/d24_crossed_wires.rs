@@ -0,0 +1,222 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::Itertools;
+
+use crate::{
+    day,
+    utils::{AocError, Day, read_lines},
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Op {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a & b,
+            Op::Or => a | b,
+            Op::Xor => a ^ b,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Gate<'a> {
+    inputs: [&'a str; 2],
+    op: Op,
+    output: &'a str,
+}
+
+impl Gate<'_> {
+    // Whether the gate reads straight from the `x` and `y` inputs.
+    fn is_first_level(&self) -> bool {
+        self.inputs.iter().all(|w| w.starts_with('x') || w.starts_with('y'))
+    }
+}
+
+struct Circuit<'a> {
+    wires: HashMap<&'a str, bool>,
+    gates: Vec<Gate<'a>>,
+}
+
+fn parse_circuit(input: &str) -> Result<Circuit<'_>, AocError> {
+    let mut lines = read_lines(input).enumerate();
+
+    let wires = lines
+        .by_ref()
+        .take_while(|(_, line)| !line.is_empty())
+        .map(|(y, line)| {
+            let value = match line.split_once(": ") {
+                Some((wire, "0")) => (wire, false),
+                Some((wire, "1")) => (wire, true),
+                _ => return Err(AocError::expected("a wire like 'x00: 1'").on_line(y)),
+            };
+            Ok(value)
+        })
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    let gates: Vec<_> = lines
+        .map(|(y, line)| {
+            let invalid = || AocError::expected("a gate like 'x00 AND y00 -> z00'").on_line(y);
+            let [a, op, b, "->", output] = line.split_whitespace().collect_vec()[..] else {
+                return Err(invalid());
+            };
+            let op = match op {
+                "AND" => Op::And,
+                "OR" => Op::Or,
+                "XOR" => Op::Xor,
+                _ => return Err(invalid()),
+            };
+            Ok(Gate { inputs: [a, b], op, output })
+        })
+        .try_collect()?;
+
+    if gates.is_empty() {
+        return Err(AocError::expected("at least one gate"));
+    }
+    Ok(Circuit { wires, gates })
+}
+
+impl<'a> Circuit<'a> {
+    // Resolves every gate once both of its inputs are known, in topological order.
+    fn evaluate(&self) -> Result<HashMap<&'a str, bool>, AocError> {
+        let mut values = self.wires.clone();
+        let mut waiting: HashMap<&str, Vec<&Gate>> = HashMap::new();
+        for gate in &self.gates {
+            for input in gate.inputs {
+                waiting.entry(input).or_default().push(gate);
+            }
+        }
+
+        let mut ready: VecDeque<_> = self.wires.keys().copied().collect();
+        while let Some(wire) = ready.pop_front() {
+            for gate in waiting.remove(wire).unwrap_or_default() {
+                let [a, b] = gate.inputs.map(|input| values.get(input).copied());
+                if let (Some(a), Some(b)) = (a, b)
+                    && !values.contains_key(gate.output)
+                {
+                    values.insert(gate.output, gate.op.apply(a, b));
+                    ready.push_back(gate.output);
+                }
+            }
+        }
+
+        match self.gates.iter().find(|gate| !values.contains_key(gate.output)) {
+            Some(gate) => Err(AocError::expected(format!("a value for {}", gate.output))),
+            None => Ok(values),
+        }
+    }
+
+    fn output(&self) -> Result<u64, AocError> {
+        let values = self.evaluate()?;
+        let bits = values.iter().filter(|(wire, _)| wire.starts_with('z')).sorted().rev();
+        if bits.len() > 64 {
+            return Err(AocError::expected("at most 64 z wires"));
+        }
+        Ok(bits.fold(0, |n, (_, &bit)| n << 1 | bit as u64))
+    }
+
+    fn feeds(&self, wire: &str, op: Op) -> bool {
+        self.gates.iter().any(|gate| gate.op == op && gate.inputs.contains(&wire))
+    }
+
+    // Checks every gate against the shape of a ripple-carry adder, where bit `i` is
+    //   s = x XOR y, z = s XOR carry, carry' = (x AND y) OR (s AND carry),
+    // and bit 0 is a half adder. Only the wrong outputs are reported, not how they pair up.
+    fn miswired(&self) -> Vec<&'a str> {
+        let last_z = self.gates.iter().map(|gate| gate.output).filter(|w| w.starts_with('z')).max();
+
+        self.gates
+            .iter()
+            .filter(|gate| {
+                let bit_0 = gate.inputs.contains(&"x00");
+                match gate.op {
+                    _ if Some(gate.output) == last_z => gate.op != Op::Or,
+                    _ if gate.output.starts_with('z') => {
+                        gate.op != Op::Xor || (gate.is_first_level() && !bit_0)
+                    }
+                    _ if bit_0 => false,
+                    Op::Xor if gate.is_first_level() => !self.feeds(gate.output, Op::Xor),
+                    Op::Xor => true,
+                    Op::And => !self.feeds(gate.output, Op::Or),
+                    Op::Or => false,
+                }
+            })
+            .map(|gate| gate.output)
+            .sorted()
+            .dedup()
+            .collect()
+    }
+}
+
+fn p1(input: &str) -> Result<u64, AocError> {
+    parse_circuit(input)?.output()
+}
+
+fn p2(input: &str) -> Result<String, AocError> {
+    Ok(parse_circuit(input)?.miswired().join(","))
+}
+
+day! { 24: Day<u64, String>,
+    part_1: { examples: ["example_1.txt" => 4, "example_2.txt" => 2024], func: p1 },
+    part_2: { examples: [], func: p2 }
+}
+
+#[cfg(test)]
+mod d24_tests {
+    use super::*;
+
+    // A `bits`-wide ripple-carry adder with the two listed outputs swapped.
+    fn adder(bits: usize, x: u64, y: u64, swap: [&str; 2]) -> String {
+        let wires = (0..bits)
+            .flat_map(|i| [format!("x{i:02}: {}", x >> i & 1), format!("y{i:02}: {}", y >> i & 1)]);
+        let mut gates = vec!["x00 XOR y00 -> z00".to_string(), "x00 AND y00 -> c00".to_string()];
+        for i in 1..bits {
+            let carry = match i + 1 == bits {
+                true => format!("z{:02}", i + 1),
+                false => format!("c{i:02}"),
+            };
+            let p = i - 1;
+            gates.extend([
+                format!("x{i:02} XOR y{i:02} -> s{i:02}"),
+                format!("s{i:02} XOR c{p:02} -> z{i:02}"),
+                format!("x{i:02} AND y{i:02} -> a{i:02}"),
+                format!("s{i:02} AND c{p:02} -> b{i:02}"),
+                format!("a{i:02} OR b{i:02} -> {carry}"),
+            ]);
+        }
+        let gates = gates.into_iter().map(|gate| {
+            let (wiring, output) = gate.split_once(" -> ").unwrap();
+            let output = match swap.iter().position(|&w| w == output) {
+                Some(i) => swap[1 - i],
+                None => output,
+            };
+            format!("{wiring} -> {output}")
+        });
+        wires.chain([String::new()]).chain(gates).join("\n")
+    }
+
+    #[test]
+    fn adds() {
+        assert_eq!(p1(&adder(6, 45, 29, ["", ""])).unwrap(), 74);
+        assert_eq!(p2(&adder(6, 45, 29, ["", ""])).unwrap(), "");
+    }
+
+    #[test]
+    fn finds_swapped_outputs() {
+        assert_eq!(p2(&adder(6, 0, 0, ["z03", "a03"])).unwrap(), "a03,z03");
+        assert_eq!(p2(&adder(6, 0, 0, ["s02", "a02"])).unwrap(), "a02,s02");
+        assert_eq!(p2(&adder(6, 0, 0, ["z04", "c04"])).unwrap(), "c04,z04");
+    }
+
+    #[test]
+    fn rejects_loops() {
+        assert!(p1("x00: 1\n\nx00 AND a -> b\nb OR x00 -> a\n").is_err());
+        assert!(p1("x00: 1\n\nx00 NAND x00 -> z00\n").is_err());
+        assert!(p1("").is_err());
+    }
+}
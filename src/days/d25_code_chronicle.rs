@@ -0,0 +1,105 @@
+use crate::{
+    day,
+    utils::{AocError, Day, read_lines},
+};
+
+// Heights of the pins of a lock, or of the cuts of a key, column by column.
+struct Schematics {
+    locks: Vec<Vec<usize>>,
+    keys: Vec<Vec<usize>>,
+    space: usize,
+}
+
+fn parse_schematics(input: &str) -> Result<Schematics, AocError> {
+    let (mut locks, mut keys, mut space) = (vec![], vec![], None);
+    let mut lines = read_lines(input).enumerate().peekable();
+
+    while lines.peek().is_some() {
+        let rows: Vec<_> = lines.by_ref().take_while(|(_, line)| !line.is_empty()).collect();
+        let (Some(&(first, top)), Some(&(_, bottom))) = (rows.first(), rows.last()) else {
+            continue;
+        };
+
+        let mut heights = vec![0; top.len()];
+        for &(y, line) in &rows {
+            if line.len() != top.len() {
+                return Err(AocError::expected(format!("{} columns", top.len())).on_line(y));
+            }
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => heights[x] += 1,
+                    '.' => {}
+                    _ => return Err(AocError::unexpected_char(y, x, c)),
+                }
+            }
+        }
+
+        // Both kinds have one full row, at the top for locks and at the bottom for keys.
+        let full = |row: &str| row.chars().all(|c| c == '#');
+        let schematics = match (full(top), full(bottom)) {
+            _ if rows.len() < 2 => None,
+            (true, _) => Some(&mut locks),
+            (false, true) => Some(&mut keys),
+            (false, false) => None,
+        };
+        let schematics = schematics.ok_or_else(|| {
+            AocError::expected("a schematic with a full top or bottom row").on_line(first)
+        })?;
+
+        let expected = *space.get_or_insert(rows.len() - 2);
+        if expected != rows.len() - 2 {
+            return Err(AocError::expected(format!("{} rows", expected + 2)).on_line(first));
+        }
+
+        schematics.push(heights.into_iter().map(|h| h - 1).collect());
+    }
+
+    let space = space.ok_or_else(|| AocError::expected("lock and key schematics"))?;
+    Ok(Schematics { locks, keys, space })
+}
+
+fn p1(input: &str) -> Result<usize, AocError> {
+    let Schematics { locks, keys, space } = parse_schematics(input)?;
+    let fits = |lock: &Vec<usize>, key: &Vec<usize>| {
+        std::iter::zip(lock, key).all(|(pin, cut)| pin + cut <= space)
+    };
+    Ok(locks.iter().map(|lock| keys.iter().filter(|key| fits(lock, key)).count()).sum())
+}
+
+// The last star comes for free once the other 49 are collected.
+fn p2(input: &str) -> Result<&'static str, AocError> {
+    parse_schematics(input)?;
+    Ok("Merry Christmas!")
+}
+
+day! { 25: Day<usize, &'static str>,
+    part_1: { examples: ["example.txt" => 3], func: p1 },
+    part_2: { examples: [], func: p2 }
+}
+
+#[cfg(test)]
+mod d25_tests {
+    use super::*;
+    use crate::utils::{input_path, read_input};
+
+    #[test]
+    fn heights() {
+        let input = read_input(input_path(SOLUTION.part_1.examples[0].file)).unwrap();
+        let schematics = parse_schematics(&input).unwrap();
+        assert_eq!(schematics.locks, [[0, 5, 3, 4, 3], [1, 2, 0, 5, 3]]);
+        assert_eq!(schematics.keys[0], [5, 0, 2, 1, 3]);
+        assert_eq!(schematics.space, 5);
+    }
+
+    #[test]
+    fn rejects_ragged_schematics() {
+        assert!(parse_schematics("###\n#.#\n...\n\n...\n.#\n###\n").is_err());
+        assert!(parse_schematics("###\n#x#\n...\n").is_err());
+        assert!(parse_schematics("#####\n").is_err());
+        assert!(parse_schematics(".....\n.....\n").is_err());
+        assert!(parse_schematics("").is_err());
+
+        let err = parse_schematics("##\n..\n\n..\n..\n##\n").err().unwrap();
+        assert_eq!(err.to_string(), "4: expected 2 rows");
+    }
+}
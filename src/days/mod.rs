@@ -18,3 +18,79 @@ pub mod d17_chronospatial_computer;
 pub mod d18_ram_run;
 pub mod d19_linen_layout;
 pub mod d20_race_condition;
+pub mod d21_keypad_conundrum;
+pub mod d22_monkey_market;
+pub mod d23_lan_party;
+pub mod d24_crossed_wires;
+pub mod d25_code_chronicle;
+
+use crate::utils::Solution;
+
+// Every module above also needs an entry here; `calendar_is_complete` catches a missing one.
+macro_rules! days {
+    ($($module:ident),* $(,)?) => {
+        pub const ALL: &[&dyn Solution] = &[$(&$module::SOLUTION),*];
+    };
+}
+
+days! {
+    d01_historian_hysteria,
+    d02_red_nosed_reports,
+    d03_mull_it_over,
+    d04_ceres_search,
+    d05_print_queue,
+    d06_guard_gallivant,
+    d07_bridge_repair,
+    d08_resonant_collinearity,
+    d09_disk_fragmenter,
+    d10_hoof_it,
+    d11_plutonian_pebbles,
+    d12_garden_groups,
+    d13_claw_contraption,
+    d14_restroom_redoubt,
+    d15_warehouse_woes,
+    d16_reindeer_maze,
+    d17_chronospatial_computer,
+    d18_ram_run,
+    d19_linen_layout,
+    d20_race_condition,
+    d21_keypad_conundrum,
+    d22_monkey_market,
+    d23_lan_party,
+    d24_crossed_wires,
+    d25_code_chronicle,
+}
+
+pub fn find(day: usize) -> Option<&'static dyn Solution> {
+    ALL.iter().copied().find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod registry_tests {
+    use super::*;
+
+    #[test]
+    fn days_match_module_names() {
+        ALL.iter().for_each(|solution| {
+            let prefix = format!("d{:02}_", solution.day());
+            assert!(solution.name().starts_with(&prefix), "{}", solution.name());
+        });
+    }
+
+    #[test]
+    fn days_are_sorted_and_unique() {
+        assert!(ALL.array_windows().all(|[a, b]| a.day() < b.day()));
+    }
+
+    #[test]
+    fn calendar_is_complete() {
+        assert!(ALL.iter().map(|solution| solution.day()).eq(1..=25));
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(find(16).unwrap().title(), "Reindeer Maze");
+        assert_eq!(find(1).unwrap().title(), "Historian Hysteria");
+        assert_eq!(find(25).unwrap().title(), "Code Chronicle");
+    }
+}
@@ -10,4 +10,5 @@
 extern crate core;
 
 pub mod days;
+pub mod runner;
 pub mod utils;
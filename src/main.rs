@@ -1,35 +1,57 @@
-use advent_of_code_2024::{days::*, utils::Solution};
+use advent_of_code_2024::{
+    days,
+    runner::{
+        self,
+        cli::{Command, Days},
+    },
+    utils,
+};
 
 fn main() {
-    run_day(20);
-}
+    let args = match Command::parse(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::List) => {
+            days::ALL.iter().for_each(|solution| {
+                println!("{:02} {}", solution.day(), solution.title());
+            });
+            return;
+        }
+        Ok(Command::New { day, name }) => match runner::scaffold::new_day(day, &name) {
+            Ok(module) => {
+                println!("created src/days/{module}.rs and inputs/day_{day:02}/");
+                return;
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                std::process::exit(1);
+            }
+        },
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(inputs) = &args.inputs {
+        utils::set_inputs_root(inputs);
+    }
 
-fn run_day(day_number: usize) {
-    #[allow(clippy::zero_prefixed_literal)]
-    let solution: &dyn Solution = match day_number {
-        01 => &d01_historian_hysteria::SOLUTION,
-        02 => &d02_red_nosed_reports::SOLUTION,
-        03 => &d03_mull_it_over::SOLUTION,
-        04 => &d04_ceres_search::SOLUTION,
-        05 => &d05_print_queue::SOLUTION,
-        06 => &d06_guard_gallivant::SOLUTION,
-        07 => &d07_bridge_repair::SOLUTION,
-        08 => &d08_resonant_collinearity::SOLUTION,
-        09 => &d09_disk_fragmenter::SOLUTION,
-        10 => &d10_hoof_it::SOLUTION,
-        11 => &d11_plutonian_pebbles::SOLUTION,
-        12 => &d12_garden_groups::SOLUTION,
-        13 => &d13_claw_contraption::SOLUTION,
-        14 => &d14_restroom_redoubt::SOLUTION,
-        15 => &d15_warehouse_woes::SOLUTION,
-        16 => &d16_reindeer_maze::SOLUTION,
-        17 => &d17_chronospatial_computer::SOLUTION,
-        18 => &d18_ram_run::SOLUTION,
-        19 => &d19_linen_layout::SOLUTION,
-        20 => &d20_race_condition::SOLUTION,
-        _ => unreachable!(),
+    let solutions = match &args.days {
+        Days::All => days::ALL.to_vec(),
+        Days::List(days) => days
+            .iter()
+            .map(|&day| days::find(day).ok_or_else(|| format!("no solution for day {day}")))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|err| {
+                eprintln!("{err}");
+                std::process::exit(2);
+            }),
     };
 
-    solution.run_part_1();
-    solution.run_part_2();
+    if args.watch {
+        runner::watch::watch(&solutions, &args);
+    }
+    if !runner::run(&solutions, &args) {
+        std::process::exit(1);
+    }
 }
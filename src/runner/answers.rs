@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+
+use crate::utils::{AocError, Part, read_input, read_lines};
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail(String),
+    Missing,
+}
+
+impl Check {
+    pub fn new(answer: &str, expected: Option<&str>) -> Self {
+        match expected {
+            None => Check::Missing,
+            Some(expected) if expected == answer => Check::Pass,
+            Some(expected) => Check::Fail(expected.to_string()),
+        }
+    }
+}
+
+pub fn answers_path(task: &Path) -> PathBuf {
+    task.with_file_name(ANSWERS_FILE)
+}
+
+// one `p1: <answer>` or `p2: <answer>` per line, a missing file means no stored answers;
+// like inputs, a file on disk takes precedence over the embedded answers
+pub fn load(path: &Path, embedded: Option<&str>) -> Result<[Option<String>; 2], AocError> {
+    let mut answers = [None, None];
+    let input = match embedded {
+        _ if path.exists() => read_input(path)?,
+        Some(embedded) => embedded.to_string(),
+        None => return Ok(answers),
+    };
+    for (y, line) in read_lines(&input).enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let part = match line.split_once(':') {
+            Some((p, answer)) if p.trim() == "p1" => Some((Part::One, answer)),
+            Some((p, answer)) if p.trim() == "p2" => Some((Part::Two, answer)),
+            _ => None,
+        };
+        let (part, answer) = part.ok_or_else(|| {
+            AocError::expected("'p1: <answer>' or 'p2: <answer>'").on_line(y).in_file(path)
+        })?;
+        answers[part.number() - 1] = Some(answer.trim().to_string());
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod answers_tests {
+    use super::*;
+
+    #[test]
+    fn embedded_answers() {
+        let missing = Path::new("/nonexistent/answers.txt");
+        assert_eq!(load(missing, None).unwrap(), [None, None]);
+        assert_eq!(load(missing, Some("p2: 4,6\n")).unwrap(), [None, Some("4,6".to_string())]);
+    }
+}
@@ -0,0 +1,268 @@
+use std::{
+    num::NonZeroUsize,
+    path::{MAIN_SEPARATOR, Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::utils::Part;
+
+pub const USAGE: &str = "\
+usage: aoc <days> [part] [input] [options]
+       aoc --all [part] [input] [options]
+       aoc list
+       aoc new <day> <name>
+
+  days   a day, a range or a list of both: 16, 1-5, 1,3,10-12, all
+  name   the new day's module name in snake_case, e.g. keypad_conundrum
+  part   1, 2 or both (default: both)
+  input  task (default), an example file name such as example_1.txt, a path,
+         or - to read from stdin
+
+options:
+  --all           run every day, same as giving all as <days>
+  --jobs <n>      solve up to n parts at once on separate threads (default: 1);
+                  results are still reported in day order
+  --timeout <s>   give up on a part after s seconds, report it and carry on
+  --watch         run with timings again whenever an example or task file
+                  of the days changes
+  --time          print answers and timings as a table
+  --repeat <n>    solve every part n times, report min and median (implies --time)
+  --verify        compare task answers with day_NN/answers.txt
+                  and example answers with the expectations declared in day!
+  --inputs <dir>  directory with the day_NN input folders
+                  (default: $AOC_INPUTS, or inputs/ in the crate directory)
+  --format <fmt>  text (default), or json lines / csv with day, part, input,
+                  answer, elapsed_ns, status and message; status is one of
+                  ok, error, timeout or mismatch";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Days {
+    All,
+    List(Vec<usize>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Input {
+    Task,
+    Example(String),
+    Path(PathBuf),
+    Stdin,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Command {
+    List,
+    New { day: usize, name: String },
+    Run(Args),
+}
+
+impl Command {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.into_iter().peekable();
+        match args.peek().map(String::as_str) {
+            Some("list") => match args.nth(1) {
+                Some(arg) => Err(format!("unexpected argument '{arg}'\n\n{USAGE}")),
+                None => Ok(Command::List),
+            },
+            Some("new") => {
+                let (day, name) = match (args.nth(1), args.next(), args.next()) {
+                    (Some(day), Some(name), None) => (day, name),
+                    _ => return Err(USAGE.to_string()),
+                };
+                let day = parse_day(&day)?;
+                match name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                {
+                    true if !name.is_empty() => Ok(Command::New { day, name }),
+                    _ => Err(format!("invalid name '{name}', expected snake_case")),
+                }
+            }
+            _ => Args::parse(args).map(Command::Run),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Args {
+    pub days: Days,
+    pub parts: Vec<Part>,
+    pub input: Input,
+    pub time: bool,
+    pub repeat: usize,
+    pub verify: bool,
+    pub inputs: Option<PathBuf>,
+    pub format: Format,
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+    pub watch: bool,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut positional = vec![];
+        let (mut time, mut repeat, mut verify) = (false, 1, false);
+        let (mut inputs, mut format) = (None, Format::Text);
+        let (mut all, mut jobs, mut timeout, mut watch) = (false, 1, None, false);
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                "--time" => time = true,
+                "--verify" => verify = true,
+                "--inputs" => inputs = Some(option_value::<PathBuf>(&arg, args.next())?),
+                "--format" => format = option_value(&arg, args.next())?,
+                "--all" => all = true,
+                "--watch" => watch = true,
+                "--jobs" => jobs = option_value::<NonZeroUsize>(&arg, args.next())?.get(),
+                "--timeout" => {
+                    let secs = option_value::<f64>(&arg, args.next())?;
+                    match Duration::try_from_secs_f64(secs) {
+                        Ok(t) if !t.is_zero() => timeout = Some(t),
+                        _ => return Err(format!("invalid value for '{arg}'\n\n{USAGE}")),
+                    }
+                }
+                "--repeat" => {
+                    repeat = option_value::<NonZeroUsize>(&arg, args.next())?.get();
+                    time = true;
+                }
+                _ if arg.starts_with("--") => {
+                    return Err(format!("unknown option '{arg}'\n\n{USAGE}"));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut args = positional.into_iter().peekable();
+
+        let days = match all {
+            true => Days::All,
+            false => match args.next() {
+                None => return Err(USAGE.to_string()),
+                Some(arg) => parse_days(&arg)?,
+            },
+        };
+
+        let parts = match args.next_if(|arg| parse_parts(arg).is_some()) {
+            Some(arg) => parse_parts(&arg).unwrap(),
+            None => vec![Part::One, Part::Two],
+        };
+
+        let input = args.next().map(|arg| parse_input(&arg)).unwrap_or(Input::Task);
+
+        match args.next() {
+            Some(arg) => Err(format!("unexpected argument '{arg}'\n\n{USAGE}")),
+            None if watch && input == Input::Stdin => {
+                Err(format!("--watch cannot read the input from stdin\n\n{USAGE}"))
+            }
+            None => Ok(Self {
+                days,
+                parts,
+                input,
+                time,
+                repeat,
+                verify,
+                inputs,
+                format,
+                jobs,
+                timeout,
+                watch,
+            }),
+        }
+    }
+}
+
+fn option_value<T: FromStr>(option: &str, value: Option<String>) -> Result<T, String> {
+    match value.as_deref().map(str::parse) {
+        Some(Ok(value)) => Ok(value),
+        Some(Err(_)) => Err(format!("invalid value for '{option}'\n\n{USAGE}")),
+        None => Err(format!("missing value for '{option}'\n\n{USAGE}")),
+    }
+}
+
+fn parse_day(s: &str) -> Result<usize, String> {
+    match s.trim().parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("invalid day '{s}', expected 1 to 25")),
+    }
+}
+
+fn parse_days(arg: &str) -> Result<Days, String> {
+    if arg == "all" {
+        return Ok(Days::All);
+    }
+
+    let parse_day = |s: &str| parse_day(s).map_err(|err| format!("{err}\n\n{USAGE}"));
+
+    let mut days = vec![];
+    for chunk in arg.split(',') {
+        match chunk.split_once('-') {
+            Some((from, to)) => match (parse_day(from)?, parse_day(to)?) {
+                (from, to) if from <= to => days.extend(from..=to),
+                _ => return Err(format!("invalid range '{chunk}'\n\n{USAGE}")),
+            },
+            None => days.push(parse_day(chunk)?),
+        }
+    }
+    Ok(Days::List(days))
+}
+
+fn parse_parts(arg: &str) -> Option<Vec<Part>> {
+    match arg {
+        "1" => Some(vec![Part::One]),
+        "2" => Some(vec![Part::Two]),
+        "both" => Some(vec![Part::One, Part::Two]),
+        _ => None,
+    }
+}
+
+fn parse_input(arg: &str) -> Input {
+    match arg {
+        "task" => Input::Task,
+        "-" => Input::Stdin,
+        _ if arg.contains(MAIN_SEPARATOR) || Path::new(arg).is_file() => Input::Path(arg.into()),
+        _ => Input::Example(arg.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    #[test]
+    fn day_lists() {
+        assert_eq!(parse_days("1,3-5"), Ok(Days::List(vec![1, 3, 4, 5])));
+        assert_eq!(parse_days("5-5"), Ok(Days::List(vec![5])));
+        assert!(parse_days("5-3").unwrap_err().starts_with("invalid range '5-3'"));
+        assert!(parse_days("1-1000000000").unwrap_err().starts_with("invalid day '1000000000'"));
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("0").is_err());
+    }
+
+    #[test]
+    fn watch_needs_files() {
+        let parse = |args: &[&str]| Args::parse(args.iter().map(|arg| arg.to_string()));
+        assert!(parse(&["1", "-", "--watch"]).unwrap_err().starts_with("--watch cannot read"));
+        assert!(parse(&["1", "-"]).is_ok());
+    }
+}
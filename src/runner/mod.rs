@@ -0,0 +1,385 @@
+pub mod answers;
+pub mod cli;
+mod report;
+pub mod scaffold;
+pub mod watch;
+
+use std::{
+    any::Any,
+    io::Read,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    time::{Duration, Instant},
+};
+
+use answers::Check;
+use cli::{Args, Format, Input};
+
+use crate::utils::{
+    AocError,
+    CancelToken,
+    Parsed,
+    Part,
+    Solution,
+    input_path,
+    read_input,
+    read_named_input,
+    with_cancel_token,
+};
+
+const STDIN: &str = "<stdin>";
+
+pub enum Source<'a> {
+    Named(&'a str),
+    File(&'a Path),
+    Stdin(&'a str, Duration),
+}
+
+impl Source<'_> {
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Source::Named(file) => input_path(file),
+            Source::File(path) => path.to_path_buf(),
+            Source::Stdin(..) => STDIN.into(),
+        }
+    }
+}
+
+pub struct Measurement {
+    pub day: usize,
+    pub title: String,
+    pub part: Part,
+    pub input: Option<PathBuf>,
+    pub answer: Result<String, String>,
+    pub read: Duration,
+    pub parse: Duration,
+    pub solve: Vec<Duration>,
+    pub check: Option<Check>,
+    pub timed_out: bool,
+}
+
+impl Measurement {
+    pub fn solve_min(&self) -> Duration {
+        self.solve.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn solve_median(&self) -> Duration {
+        let mut solve = self.solve.clone();
+        solve.sort();
+        solve.get(solve.len() / 2).copied().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.read + self.parse + self.solve_median()
+    }
+}
+
+// An input read and parsed once, shared by every part that runs on the same source.
+struct Prepared {
+    day: usize,
+    path: PathBuf,
+    parsed: Result<Arc<dyn Parsed>, String>,
+    read: Duration,
+    parse: Duration,
+}
+
+pub fn run(solutions: &[&'static dyn Solution], args: &Args) -> bool {
+    let mut all_ok = true;
+
+    let stdin = match args.input {
+        Input::Stdin => match read_stdin() {
+            Ok(stdin) => Some(stdin),
+            Err(err) => {
+                eprintln!("error: {err}");
+                return false;
+            }
+        },
+        _ => None,
+    };
+
+    if args.format == Format::Csv {
+        report::print_csv_header();
+    }
+
+    let answers: Vec<_> = solutions
+        .iter()
+        .map(|solution| match (args.verify, &args.input) {
+            (true, Input::Task) => {
+                let path = answers::answers_path(&input_path(solution.task(Part::One)));
+                answers::load(&path, solution.embedded_answers()).unwrap_or_else(|err| {
+                    eprintln!("d{:02}: error: {err}", solution.day());
+                    all_ok = false;
+                    [None, None]
+                })
+            }
+            _ => [None, None],
+        })
+        .collect();
+
+    let jobs: Vec<_> =
+        (0..solutions.len()).flat_map(|i| args.parts.iter().map(move |&part| (i, part))).collect();
+    let next_job = AtomicUsize::new(0);
+    let mut results: Vec<Option<Measurement>> = jobs.iter().map(|_| None).collect();
+
+    // Workers pick up parts in day order; results are printed as soon as every earlier one is in.
+    std::thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..args.jobs.min(jobs.len()) {
+            let (tx, jobs, next_job, answers) = (tx.clone(), &jobs, &next_job, &answers);
+            let stdin = stdin.as_ref().map(|(input, read)| (input.as_str(), *read));
+            scope.spawn(move || {
+                let mut prepared = None;
+                loop {
+                    let n = next_job.fetch_add(1, Ordering::Relaxed);
+                    let Some(&(i, part)) = jobs.get(n) else { break };
+                    let solution = solutions[i];
+                    let m = panic::catch_unwind(AssertUnwindSafe(|| {
+                        run_part(solution, part, args, stdin, &answers[i], &mut prepared)
+                    }))
+                    .unwrap_or_else(|payload| {
+                        prepared = None;
+                        failed(solution, part, format!("panicked: {}", panic_message(&*payload)))
+                    });
+
+                    if tx.send((n, m)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut printed = 0;
+        for (i, m) in rx {
+            results[i] = Some(m);
+            while let Some(Some(m)) = results.get(printed) {
+                match args.format {
+                    Format::Text if !args.time => report::print_line(m),
+                    Format::Text => {}
+                    Format::Json => report::print_json(m),
+                    Format::Csv => report::print_csv(m),
+                }
+                printed += 1;
+            }
+        }
+    });
+    let measurements: Vec<_> = results.into_iter().flatten().collect();
+
+    if args.format == Format::Text && args.time {
+        report::print_table(&measurements, args.repeat, args.verify);
+    }
+    if args.format == Format::Text && args.verify {
+        report::print_summary(&measurements);
+    }
+
+    all_ok
+        && measurements.iter().all(|m| m.answer.is_ok() && !matches!(m.check, Some(Check::Fail(_))))
+}
+
+fn run_part(
+    solution: &'static dyn Solution,
+    part: Part,
+    args: &Args,
+    stdin: Option<(&str, Duration)>,
+    answers: &[Option<String>; 2],
+    prepared: &mut Option<Prepared>,
+) -> Measurement {
+    let (mut m, expected) = match resolve_input(solution, part, &args.input, stdin) {
+        Ok((source, expected)) => {
+            let prepared = match prepared {
+                // Reading and parsing are only reported for the part that did them.
+                Some(p) if p.day == solution.day() && p.path == source.path() => {
+                    (p.read, p.parse) = (Duration::ZERO, Duration::ZERO);
+                    p
+                }
+                slot => slot.insert(prepare(solution, part, &source)),
+            };
+            (measure(solution, part, prepared, args.repeat, args.timeout), expected)
+        }
+        Err(err) => (failed(solution, part, err), None),
+    };
+
+    if args.verify {
+        let expected = expected.as_deref().or(answers[part.number() - 1].as_deref());
+        m.check = m.answer.as_ref().ok().map(|answer| Check::new(answer, expected));
+    }
+    m
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload.downcast_ref::<String>().map_or("unknown cause", String::as_str),
+    }
+}
+
+fn read_stdin() -> Result<(String, Duration), AocError> {
+    let start = Instant::now();
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|err| AocError::from(err).in_file(STDIN))?;
+    Ok((input, start.elapsed()))
+}
+
+fn prepare(solution: &'static dyn Solution, part: Part, source: &Source) -> Prepared {
+    let start = Instant::now();
+    let input = match *source {
+        Source::Named(file) => read_named_input(file, solution.embedded(part, file)),
+        Source::File(path) => read_input(path),
+        Source::Stdin(input, _) => Ok(input.to_string()),
+    };
+    let read = match *source {
+        Source::Stdin(_, read) => read,
+        _ => start.elapsed(),
+    };
+
+    let start = Instant::now();
+    let parsed = input
+        .and_then(|input| solution.parse(&input))
+        .map(Arc::from)
+        .map_err(|err| err.in_file(source.path()).to_string());
+    let parse = start.elapsed();
+
+    Prepared { day: solution.day(), path: source.path(), parsed, read, parse }
+}
+
+fn measure(
+    solution: &dyn Solution,
+    part: Part,
+    prepared: &Prepared,
+    repeat: usize,
+    timeout: Option<Duration>,
+) -> Measurement {
+    let parsed = match &prepared.parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let (input, read, parse) = (Some(prepared.path.clone()), prepared.read, prepared.parse);
+            return Measurement { input, read, parse, ..failed(solution, part, err.clone()) };
+        }
+    };
+
+    let mut solve = Vec::with_capacity(repeat);
+    let mut timed_out = false;
+    let answer = loop {
+        let res = match timeout {
+            None => Some(timed(|| parsed.solve(part))),
+            Some(timeout) => solve_with_timeout(parsed, part, timeout),
+        };
+        let Some((answer, elapsed)) = res else {
+            timed_out = true;
+            solve.push(timeout.unwrap_or_default());
+            break Err(format!("timed out after {:.2?}", timeout.unwrap_or_default()));
+        };
+        solve.push(elapsed);
+
+        if answer.is_err() || solve.len() >= repeat {
+            break answer.map_err(|err| err.in_file(&prepared.path).to_string());
+        }
+    };
+
+    Measurement {
+        day: solution.day(),
+        title: solution.title(),
+        part,
+        input: Some(prepared.path.clone()),
+        answer,
+        read: prepared.read,
+        parse: prepared.parse,
+        solve,
+        check: None,
+        timed_out,
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+// Solves on a thread of its own, so that a part stuck in a loop without cancellation checks
+// is simply left behind. It keeps a core busy though, so later timings are less reliable.
+fn solve_with_timeout(
+    parsed: &Arc<dyn Parsed>,
+    part: Part,
+    timeout: Duration,
+) -> Option<(Result<String, AocError>, Duration)> {
+    let (tx, rx) = mpsc::channel();
+    let token = CancelToken::new();
+    let handle = {
+        let (parsed, token) = (Arc::clone(parsed), token.clone());
+        std::thread::spawn(move || {
+            let res = timed(|| with_cancel_token(token, || parsed.solve(part)));
+            let _ = tx.send(res);
+        })
+    };
+
+    match rx.recv_timeout(timeout) {
+        Ok(res) => Some(res),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            None
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let payload = handle.join().expect_err("the solver only exits after sending");
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+fn failed(solution: &dyn Solution, part: Part, err: String) -> Measurement {
+    Measurement {
+        day: solution.day(),
+        title: solution.title(),
+        part,
+        input: None,
+        answer: Err(err),
+        read: Duration::ZERO,
+        parse: Duration::ZERO,
+        solve: vec![],
+        check: None,
+        timed_out: false,
+    }
+}
+
+fn resolve_input<'a>(
+    solution: &'a dyn Solution,
+    part: Part,
+    input: &'a Input,
+    stdin: Option<(&'a str, Duration)>,
+) -> Result<(Source<'a>, Option<String>), String> {
+    match input {
+        Input::Task => Ok((Source::Named(solution.task(part)), None)),
+        Input::Stdin => {
+            let (input, read) = stdin.unwrap_or_default();
+            Ok((Source::Stdin(input, read), None))
+        }
+        Input::Path(path) if path.is_file() => Ok((Source::File(path), None)),
+        Input::Path(path) => Err(format!("no such file: {}", path.display())),
+        Input::Example(name) => {
+            let examples = solution.examples(part);
+            examples
+                .iter()
+                .find(|(example, _)| {
+                    let example = Path::new(example);
+                    example.file_name().is_some_and(|f| f == name.as_str())
+                        || example.file_stem().is_some_and(|f| f == name.as_str())
+                })
+                .map(|(example, expected)| (Source::Named(example), expected.clone()))
+                .ok_or_else(|| {
+                    let available = examples
+                        .iter()
+                        .flat_map(|(example, _)| Path::new(example).file_name()?.to_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("no example '{name}' (available: {available})")
+                })
+        }
+    }
+}
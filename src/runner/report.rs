@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use tabled::{builder::Builder, settings::Style};
+
+use super::{Measurement, answers::Check};
+
+pub fn print_line(m: &Measurement) {
+    match (&m.answer, &m.check) {
+        (Ok(answer), None) => println!("d{:02} p{}: {answer}", m.day, m.part.number()),
+        (Ok(answer), Some(check)) => {
+            println!("d{:02} p{}: {answer} ({})", m.day, m.part.number(), format_check(check))
+        }
+        (Err(err), _) if m.timed_out => eprintln!("d{:02} p{}: {err}", m.day, m.part.number()),
+        (Err(err), _) => eprintln!("d{:02} p{}: error: {err}", m.day, m.part.number()),
+    }
+}
+
+pub fn print_csv_header() {
+    println!("day,part,input,answer,elapsed_ns,status,message");
+}
+
+pub fn print_csv(m: &Measurement) {
+    let (answer, status, message) = fields(m);
+    let input = m.input.as_ref().map(|path| path.display().to_string()).unwrap_or_default();
+    let record = [
+        m.day.to_string(),
+        m.part.number().to_string(),
+        csv_field(&input),
+        csv_field(answer.unwrap_or_default()),
+        m.total().as_nanos().to_string(),
+        status.to_string(),
+        csv_field(message.unwrap_or_default()),
+    ];
+    println!("{}", record.join(","));
+}
+
+pub fn print_json(m: &Measurement) {
+    let (answer, status, message) = fields(m);
+    let input = m.input.as_ref().map(|path| path.display().to_string());
+    let fields = [
+        ("day", m.day.to_string()),
+        ("part", m.part.number().to_string()),
+        ("input", json_string(input.as_deref())),
+        ("answer", json_string(answer)),
+        ("elapsed_ns", m.total().as_nanos().to_string()),
+        ("status", json_string(Some(status))),
+        ("message", json_string(message)),
+    ];
+    let fields: Vec<_> = fields.iter().map(|(key, value)| format!(r#""{key}":{value}"#)).collect();
+    println!("{{{}}}", fields.join(","));
+}
+
+// The answer, `ok`, `error`, `timeout` or `mismatch`, and the error or the expected answer.
+fn fields(m: &Measurement) -> (Option<&str>, &'static str, Option<&str>) {
+    match (&m.answer, &m.check) {
+        (Ok(answer), Some(Check::Fail(expected))) => (Some(answer), "mismatch", Some(expected)),
+        (Ok(answer), _) => (Some(answer), "ok", None),
+        (Err(err), _) if m.timed_out => (None, "timeout", Some(err)),
+        (Err(err), _) => (None, "error", Some(err)),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+fn json_string(s: Option<&str>) -> String {
+    let Some(s) = s else { return "null".to_string() };
+
+    let mut res = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if c.is_control() => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+pub fn print_table(measurements: &[Measurement], repeat: usize, verify: bool) {
+    let mut builder = Builder::default();
+
+    let mut header = vec!["day", "title", "part", "answer"];
+    if verify {
+        header.push("check");
+    }
+    match repeat {
+        1 => header.extend(["read", "parse", "solve", "total"]),
+        _ => header.extend(["read", "parse", "solve (min)", "solve (median)", "total"]),
+    }
+    builder.push_record(header);
+
+    for m in measurements {
+        let mut record = vec![
+            format!("{:02}", m.day),
+            m.title.clone(),
+            m.part.number().to_string(),
+            match &m.answer {
+                Ok(answer) => answer.clone(),
+                Err(err) if m.timed_out => err.clone(),
+                Err(err) => format!("error: {err}"),
+            },
+        ];
+        if verify {
+            record.push(m.check.as_ref().map(format_check).unwrap_or_default());
+        }
+        record.push(format_duration(m.read));
+        record.push(format_duration(m.parse));
+        if repeat > 1 {
+            record.push(format_duration(m.solve_min()));
+        }
+        record.push(format_duration(m.solve_median()));
+        record.push(format_duration(m.total()));
+        builder.push_record(record);
+    }
+
+    let sum = |f: fn(&Measurement) -> Duration| measurements.iter().map(f).sum::<Duration>();
+    let mut total = vec![String::new(), "total".to_string(), String::new(), String::new()];
+    if verify {
+        total.push(String::new());
+    }
+    total.push(format_duration(sum(|m| m.read)));
+    total.push(format_duration(sum(|m| m.parse)));
+    if repeat > 1 {
+        total.push(format_duration(sum(Measurement::solve_min)));
+    }
+    total.push(format_duration(sum(Measurement::solve_median)));
+    total.push(format_duration(sum(Measurement::total)));
+    builder.push_record(total);
+
+    println!("{}", builder.build().with(Style::rounded()));
+}
+
+pub fn print_summary(measurements: &[Measurement]) {
+    let checks = measurements.iter().flat_map(|m| &m.check);
+    let count = |f: fn(&Check) -> bool| checks.clone().filter(|c| f(c)).count();
+
+    let pass = count(|c| matches!(c, Check::Pass));
+    let fail = count(|c| matches!(c, Check::Fail(_)));
+    let missing = count(|c| matches!(c, Check::Missing));
+    let timeouts = measurements.iter().filter(|m| m.timed_out).count();
+    let errors = measurements.iter().filter(|m| m.answer.is_err()).count() - timeouts;
+
+    println!(
+        "verify: {} passed, {} failed, {} missing, {} errors, {} timed out",
+        pass, fail, missing, errors, timeouts
+    );
+}
+
+fn format_check(check: &Check) -> String {
+    match check {
+        Check::Pass => "pass".to_string(),
+        Check::Fail(expected) => format!("FAIL, expected {expected}"),
+        Check::Missing => "no stored answer".to_string(),
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(json_string(None), "null");
+        assert_eq!(json_string(Some("a\"b\\c\nd")), r#""a\"b\\c\nd""#);
+        assert_eq!(csv_field("7,5,4"), r#""7,5,4""#);
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("a,\"b\""), r#""a,""b""""#);
+        assert_eq!(csv_field("42"), "42");
+    }
+}
@@ -0,0 +1,134 @@
+use std::{fs, path::Path};
+
+use crate::utils::{AocError, inputs_root};
+
+const SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/days");
+
+// Writes `src/days/dNN_<name>.rs`, declares it in `days/mod.rs` and adds it to the `days!` list,
+// then creates `day_NN/` with empty example, task and answers files. Returns the module name.
+pub fn new_day(day: usize, name: &str) -> Result<String, String> {
+    let module = format!("d{day:02}_{name}");
+    let source_dir = Path::new(SOURCE_DIR);
+
+    let registry_path = source_dir.join("mod.rs");
+    let registry =
+        fs::read_to_string(&registry_path).map_err(|err| io_error(err, &registry_path))?;
+    let registry = register(&registry, &module)?;
+
+    let module_path = source_dir.join(format!("{module}.rs"));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+    fs::write(&module_path, template(day)).map_err(|err| io_error(err, &module_path))?;
+    fs::write(&registry_path, registry).map_err(|err| io_error(err, &registry_path))?;
+
+    let inputs = inputs_root().join(format!("day_{day:02}"));
+    fs::create_dir_all(&inputs).map_err(|err| io_error(err, &inputs))?;
+    for file in ["example.txt", "task.txt", "answers.txt"].map(|file| inputs.join(file)) {
+        if !file.exists() {
+            fs::write(&file, "").map_err(|err| io_error(err, &file))?;
+        }
+    }
+
+    Ok(module)
+}
+
+fn io_error(err: std::io::Error, path: &Path) -> String {
+    AocError::from(err).in_file(path).to_string()
+}
+
+// Declares `module` and inserts it into the `days!` list, keeping both sorted.
+fn register(registry: &str, module: &str) -> Result<String, String> {
+    let registry = declare(registry, module)?;
+
+    let start = registry.find("days! {\n").ok_or("no days! list in days/mod.rs")? + 8;
+    let len = registry[start..].find('}').ok_or("unterminated days! list in days/mod.rs")?;
+
+    let mut modules: Vec<_> = registry[start..start + len]
+        .lines()
+        .map(|line| line.trim().trim_end_matches(','))
+        .filter(|line| !line.is_empty())
+        .collect();
+    if let Some(existing) = modules.iter().find(|m| m.get(..4) == module.get(..4)) {
+        return Err(format!("day already exists as {existing}"));
+    }
+    modules.push(module);
+    modules.sort();
+
+    let list: String = modules.iter().map(|m| format!("    {m},\n")).collect();
+    Ok(format!("{}{list}{}", &registry[..start], &registry[start + len..]))
+}
+
+// The `pub mod` lines stay outside `days!` so rustfmt can still find the day modules.
+fn declare(registry: &str, module: &str) -> Result<String, String> {
+    let declaration = format!("pub mod {module};\n");
+    let declarations: Vec<_> = registry.match_indices("pub mod ").map(|(i, _)| i).collect();
+    let last = *declarations.last().ok_or("no module declarations in days/mod.rs")?;
+    let at = match declarations.into_iter().find(|&i| registry[i..] > *declaration) {
+        Some(i) => i,
+        None => last + registry[last..].find('\n').map_or(registry.len() - last, |end| end + 1),
+    };
+    Ok(format!("{}{declaration}{}", &registry[..at], &registry[at..]))
+}
+
+fn template(day: usize) -> String {
+    TEMPLATE.replace("{day}", &day.to_string()).replace("{dd}", &format!("{day:02}"))
+}
+
+const TEMPLATE: &str = r#"use crate::{
+    day,
+    utils::{AocError, Day, read_lines},
+};
+
+fn p1(input: &str) -> Result<usize, AocError> {
+    Ok(read_lines(input).count())
+}
+
+fn p2(input: &str) -> Result<usize, AocError> {
+    Ok(read_lines(input).count())
+}
+
+day! { {day}: Day<usize, usize>,
+    part_1: { examples: ["example.txt"], func: p1 },
+    part_2: { examples: ["example.txt"], func: p2 }
+}
+
+#[cfg(test)]
+mod d{dd}_tests {
+    use super::*;
+
+    #[test]
+    fn playground() {
+        let res = SOLUTION.part_1.run_example(0).unwrap();
+        println!("{res}");
+    }
+}
+"#;
+
+#[cfg(test)]
+mod scaffold_tests {
+    use super::*;
+
+    const REGISTRY: &str = "pub mod d01_a;\npub mod d03_c;\n\n\
+                            days! {\n    d01_a,\n    d03_c,\n}\n\npub fn find() {}\n";
+
+    #[test]
+    fn registers_in_order() {
+        assert_eq!(
+            register(REGISTRY, "d02_b").unwrap(),
+            "pub mod d01_a;\npub mod d02_b;\npub mod d03_c;\n\n\
+             days! {\n    d01_a,\n    d02_b,\n    d03_c,\n}\n\npub fn find() {}\n"
+        );
+        assert!(
+            register(REGISTRY, "d04_d").unwrap().contains("pub mod d03_c;\npub mod d04_d;\n\n")
+        );
+        assert_eq!(register(REGISTRY, "d03_other"), Err("day already exists as d03_c".into()));
+    }
+
+    #[test]
+    fn template_names_the_day() {
+        let module = template(7);
+        assert!(module.contains("day! { 7: Day<usize, usize>,"));
+        assert!(module.contains("mod d07_tests {"));
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use super::cli::{Args, Input};
+use crate::utils::{Part, Solution, input_path};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Stamp = Option<(SystemTime, u64)>;
+
+// Runs the days once, then again with timings every time one of their inputs changes.
+pub fn watch(solutions: &[&'static dyn Solution], args: &Args) -> ! {
+    let args = Args { time: true, ..args.clone() };
+    let files = watched_files(solutions, &args.input);
+    let mut stamps: Vec<_> = files.iter().map(|file| stamp(file)).collect();
+
+    println!("watching {} files, press Ctrl-C to stop", files.len());
+    super::run(solutions, &args);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let changed: Vec<_> = std::iter::zip(&files, &mut stamps)
+            .filter_map(|(file, old)| {
+                let new = stamp(file);
+                (new != *old).then(|| {
+                    *old = new;
+                    file
+                })
+            })
+            .collect();
+
+        if !changed.is_empty() {
+            changed.iter().for_each(|file| println!("changed: {}", file.display()));
+            super::run(solutions, &args);
+        }
+    }
+}
+
+fn watched_files(solutions: &[&dyn Solution], input: &Input) -> Vec<PathBuf> {
+    let mut files = vec![];
+    if let Input::Path(path) = input {
+        files.push(path.clone());
+    }
+    for solution in solutions {
+        for part in [Part::One, Part::Two] {
+            let examples = solution.examples(part).into_iter().map(|(file, _)| file);
+            for file in examples.chain([solution.task(part)]).map(input_path) {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+    }
+    files
+}
+
+fn stamp(file: &Path) -> Stamp {
+    let metadata = fs::metadata(file).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+#[cfg(test)]
+mod watch_tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn files_of_a_day() {
+        let files = watched_files(&[days::find(16).unwrap()], &Input::Task);
+        let names: Vec<_> = files.iter().flat_map(|f| f.file_name()?.to_str()).collect();
+        assert_eq!(names, ["example_1.txt", "example_2.txt", "task.txt"]);
+
+        let files = watched_files(&[days::find(16).unwrap()], &Input::Path("maze.txt".into()));
+        assert_eq!(files.len(), 4);
+        assert_eq!(files[0], Path::new("maze.txt"));
+    }
+
+    #[test]
+    fn stamps_change_with_contents() {
+        let file = std::env::temp_dir().join(format!("aoc_watch_{}.txt", std::process::id()));
+        assert_eq!(stamp(&file), None);
+
+        fs::write(&file, "1").unwrap();
+        let before = stamp(&file);
+        fs::write(&file, "12").unwrap();
+        assert_ne!(stamp(&file), before);
+
+        fs::remove_file(&file).unwrap();
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+// An answer in the form the puzzle expects it to be typed in.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    Coord(i128, i128),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Coord(x, y) => write!(f, "{x},{y}"),
+        }
+    }
+}
+
+pub trait ToAnswer {
+    fn to_answer(&self) -> Answer;
+}
+
+macro_rules! integer_answers {
+    ($($t:ty),*) => {
+        $(
+            impl ToAnswer for $t {
+                fn to_answer(&self) -> Answer {
+                    Answer::Integer(*self as i128)
+                }
+            }
+
+            impl ToAnswer for ($t, $t) {
+                fn to_answer(&self) -> Answer {
+                    Answer::Coord(self.0 as i128, self.1 as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl ToAnswer for String {
+    fn to_answer(&self) -> Answer {
+        Answer::Text(self.clone())
+    }
+}
+
+impl ToAnswer for &str {
+    fn to_answer(&self) -> Answer {
+        Answer::Text(self.to_string())
+    }
+}
+
+impl ToAnswer for Answer {
+    fn to_answer(&self) -> Answer {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod answer_tests {
+    use super::*;
+
+    #[test]
+    fn canonical_rendering() {
+        assert_eq!(42_usize.to_answer().to_string(), "42");
+        assert_eq!((-7_i64).to_answer().to_string(), "-7");
+        assert_eq!("4,6,3".to_string().to_answer().to_string(), "4,6,3");
+        assert_eq!((6_usize, 1_usize).to_answer().to_string(), "6,1");
+        assert_eq!(3_u32.to_answer(), 3_u64.to_answer());
+    }
+}
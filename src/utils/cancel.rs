@@ -0,0 +1,64 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use super::{AocError, ErrorKind};
+
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static TOKEN: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+// Runs `f` with `token` as the one `check_cancelled` looks at on this thread.
+pub fn with_cancel_token<T>(token: CancelToken, f: impl FnOnce() -> T) -> T {
+    let prev = TOKEN.replace(Some(token));
+    let res = f();
+    TOKEN.set(prev);
+    res
+}
+
+// Loops that may never finish on unexpected input call this to give up once the runner has.
+pub fn check_cancelled() -> Result<(), AocError> {
+    match TOKEN.with_borrow(|token| token.as_ref().is_some_and(CancelToken::is_cancelled)) {
+        true => Err(AocError::new(ErrorKind::Cancelled)),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod cancel_tests {
+    use super::*;
+
+    #[test]
+    fn only_the_current_token_counts() {
+        assert!(check_cancelled().is_ok());
+
+        let token = CancelToken::new();
+        with_cancel_token(token.clone(), || {
+            assert!(check_cancelled().is_ok());
+            token.cancel();
+            assert!(check_cancelled().is_err());
+        });
+        assert!(check_cancelled().is_ok());
+    }
+}
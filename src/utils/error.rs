@@ -0,0 +1,103 @@
+use std::{fmt, io, path::PathBuf, str::FromStr};
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Io(io::Error),
+    UnexpectedChar(char),
+    InvalidNumber(String),
+    Expected(String),
+    NoAnswer,
+    Cancelled,
+}
+
+// `line` and `column` are zero-based, as they come out of `enumerate`,
+// and are printed one-based.
+#[derive(Debug)]
+pub struct AocError {
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub kind: ErrorKind,
+}
+
+impl AocError {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self { file: None, line: None, column: None, kind }
+    }
+
+    pub fn unexpected_char(line: usize, column: usize, c: char) -> Self {
+        Self::new(ErrorKind::UnexpectedChar(c)).at(line, column)
+    }
+
+    pub fn expected(what: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Expected(what.into()))
+    }
+
+    pub fn no_answer() -> Self {
+        Self::new(ErrorKind::NoAnswer)
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn in_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file.get_or_insert_with(|| file.into());
+        self
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        Self::new(ErrorKind::Io(err))
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Io(err) => write!(f, "{err}"),
+            ErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            ErrorKind::InvalidNumber(s) => write!(f, "invalid number {s:?}"),
+            ErrorKind::Expected(what) => write!(f, "expected {what}"),
+            ErrorKind::NoAnswer => write!(f, "no answer found"),
+            ErrorKind::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line + 1)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, "{}:", column + 1)?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for AocError {}
+
+// `token` must be a subslice of `line`, its offset is reported as the column
+pub fn parse_number<T: FromStr>(y: usize, line: &str, token: &str) -> Result<T, AocError> {
+    let token = token.trim();
+    token.parse().map_err(|_| {
+        let x = (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        AocError::new(ErrorKind::InvalidNumber(token.to_string())).at(y, x)
+    })
+}
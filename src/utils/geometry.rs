@@ -0,0 +1,200 @@
+use std::ops::{Add, Mul, Sub};
+
+use num::Integer;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::Up),
+            '>' => Some(Self::Right),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    // `y` grows downwards, as in the puzzle maps.
+    pub fn offset(self) -> Point<isize> {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::Right => Point::new(1, 0),
+            Self::Down => Point::new(0, 1),
+            Self::Left => Point::new(-1, 0),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Integer + Copy> Point<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        let diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        diff(self.x, other.x) + diff(self.y, other.y)
+    }
+
+    // Wraps around the edges of a `w` by `h` torus.
+    pub fn wrap(self, w: T, h: T) -> Self {
+        Self::new(self.x.mod_floor(&w), self.y.mod_floor(&h))
+    }
+}
+
+impl Point<usize> {
+    pub fn checked_add_signed(self, offset: Point<isize>) -> Option<Self> {
+        Some(Self::new(self.x.checked_add_signed(offset.x)?, self.y.checked_add_signed(offset.y)?))
+    }
+
+    // The neighbour in `dir`, as long as it stays inside `w` by `h`.
+    pub fn step(self, dir: Direction, (w, h): (usize, usize)) -> Option<Self> {
+        let next = self.checked_add_signed(dir.offset())?;
+        (next.x < w && next.y < h).then_some(next)
+    }
+
+    pub fn neighbours(self, size: (usize, usize)) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().filter_map(move |dir| self.step(dir, size))
+    }
+
+    // The neighbours along with the diagonal ones, going clockwise from the one above.
+    pub fn neighbours_8(self, size: (usize, usize)) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .flat_map(move |dir| {
+                let side = self.step(dir, size);
+                [side, side.and_then(|side| side.step(dir.turn_right(), size))]
+            })
+            .flatten()
+    }
+
+    // Every point inside `w` by `h` at most `radius` steps away, row by row.
+    pub fn within(self, radius: usize, (w, h): (usize, usize)) -> impl Iterator<Item = Self> {
+        let top = self.y.saturating_sub(radius);
+        let bottom = (self.y + radius).min(h.saturating_sub(1));
+
+        (top..=bottom).flat_map(move |y| {
+            let dx = radius - self.y.abs_diff(y);
+            let left = self.x.saturating_sub(dx);
+            let right = (self.x + dx).min(w.saturating_sub(1));
+            (left..=right).map(move |x| Self::new(x, y))
+        })
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self {
+        Self::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(Point { x, y }: Point<T>) -> Self {
+        (x, y)
+    }
+}
+
+#[cfg(test)]
+mod geometry_tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        use Direction::*;
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+        assert_eq!(Left.turn_right().turn_right(), Left.reverse());
+        assert_eq!(
+            "^>v<x".chars().map(Direction::from_char).collect::<Vec<_>>(),
+            [Some(Up), Some(Right), Some(Down), Some(Left), None]
+        );
+        assert!(Direction::ALL.iter().all(|&d| Direction::from_char(d.to_char()) == Some(d)));
+    }
+
+    #[test]
+    fn steps_stay_inside() {
+        let p = Point::new(0usize, 1);
+        assert_eq!(p.step(Direction::Left, (3, 2)), None);
+        assert_eq!(p.step(Direction::Down, (3, 2)), None);
+        assert_eq!(p.step(Direction::Up, (3, 2)), Some(Point::new(0, 0)));
+        assert_eq!(p.neighbours((3, 2)).count(), 2);
+        assert_eq!(
+            p.neighbours_8((3, 2)).collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (1, 1)].map(Point::from)
+        );
+        assert_eq!(Point::new(1usize, 1).neighbours_8((3, 3)).count(), 8);
+        assert_eq!(p.checked_add_signed(Point::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn wrapping_and_distances() {
+        assert_eq!((Point::new(2, 4) + Point::new(2, -3) * 5).wrap(11, 7), Point::new(1, 3));
+        assert_eq!(Point::new(-1, 5).manhattan(Point::new(2, 1)), 7);
+
+        let center = Point::new(1usize, 1);
+        assert_eq!(center.within(2, (10, 10)).count(), 11);
+        assert!(center.within(2, (10, 10)).all(|p| p.manhattan(center) <= 2));
+        assert_eq!(center.within(1, (2, 2)).count(), 3);
+    }
+}
@@ -0,0 +1,155 @@
+use std::{collections::HashMap, hash::Hash};
+
+// An undirected graph over nodes of type `N`, numbered in the order they first appear.
+// Neighbour lists are kept sorted so edge lookups are a binary search.
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Self { nodes: vec![], index: HashMap::new(), adjacency: vec![] }
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&i) = self.index.get(&node) {
+            return i;
+        }
+        self.index.insert(node.clone(), self.nodes.len());
+        self.nodes.push(node);
+        self.adjacency.push(vec![]);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, a: N, b: N) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        for (from, to) in [(a, b), (b, a)] {
+            if let Err(pos) = self.adjacency[from].binary_search(&to) {
+                self.adjacency[from].insert(pos, to);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, i: usize) -> &N {
+        &self.nodes[i]
+    }
+
+    pub fn find(&self, node: &N) -> Option<usize> {
+        self.index.get(node).copied()
+    }
+
+    pub fn neighbours(&self, i: usize) -> &[usize] {
+        &self.adjacency[i]
+    }
+
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.adjacency[a].binary_search(&b).is_ok()
+    }
+
+    // Every triangle once, as indices in increasing order.
+    pub fn triangles(&self) -> impl Iterator<Item = [usize; 3]> {
+        (0..self.len()).flat_map(move |a| {
+            let later = |a: usize| self.adjacency[a].iter().copied().filter(move |&b| b > a);
+            later(a).flat_map(move |b| {
+                later(b).filter(move |&c| self.connected(a, c)).map(move |c| [a, b, c])
+            })
+        })
+    }
+
+    // Bron–Kerbosch with pivoting; returns the indices of one largest clique, sorted.
+    pub fn max_clique(&self) -> Vec<usize> {
+        let mut best = vec![];
+        self.bron_kerbosch(&mut vec![], (0..self.len()).collect(), vec![], &mut best);
+        best.sort_unstable();
+        best
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: Vec<usize>,
+        mut excluded: Vec<usize>,
+        best: &mut Vec<usize>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() > best.len() {
+                *best = clique.clone();
+            }
+            return;
+        }
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+
+        // Any maximal clique contains the pivot or one of its non-neighbours.
+        let pivot = *candidates
+            .iter()
+            .chain(&excluded)
+            .max_by_key(|&&u| candidates.iter().filter(|&&v| self.connected(u, v)).count())
+            .unwrap();
+        let branches: Vec<_> =
+            candidates.iter().copied().filter(|&v| !self.connected(pivot, v)).collect();
+
+        for v in branches {
+            let within = |set: &[usize]| -> Vec<_> {
+                set.iter().copied().filter(|&u| self.connected(v, u)).collect()
+            };
+            clique.push(v);
+            self.bron_kerbosch(clique, within(&candidates), within(&excluded), best);
+            clique.pop();
+
+            candidates.retain(|&u| u != v);
+            excluded.push(v);
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Graph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(edges: I) -> Self {
+        let mut graph = Self::new();
+        edges.into_iter().for_each(|(a, b)| graph.add_edge(a, b));
+        graph
+    }
+}
+
+#[cfg(test)]
+mod graph_tests {
+    use super::*;
+
+    #[test]
+    fn edges_are_undirected() {
+        let graph: Graph<_> =
+            [("a", "b"), ("b", "c"), ("c", "a"), ("a", "b")].into_iter().collect();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.neighbours(0), [1, 2]);
+        assert!(graph.connected(2, 1));
+        assert_eq!(graph.triangles().collect::<Vec<_>>(), [[0, 1, 2]]);
+    }
+
+    #[test]
+    fn largest_clique() {
+        // A 4-clique 0..=3 with a tail, next to a triangle 4, 5, 6.
+        let edges =
+            [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 4), (4, 5), (5, 6), (6, 4)];
+        let graph: Graph<u8> = edges.into_iter().collect();
+        assert_eq!(graph.max_clique(), [0, 1, 2, 3]);
+        assert_eq!(graph.triangles().count(), 5);
+        assert!(Graph::<u8>::new().max_clique().is_empty());
+    }
+}
@@ -0,0 +1,287 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use super::{
+    AocError,
+    geometry::{Direction, Point},
+    read_lines,
+};
+
+// Where each marker was first found, if anywhere.
+pub type Markers<const N: usize> = [Option<Point<usize>>; N];
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0, "grid must be at least one cell wide");
+        assert_eq!(cells.len(), width * height, "grid cells do not match its size");
+        Self { width, height, cells }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    pub fn parse(input: &str, tile: impl FnMut(char) -> Option<T>) -> Result<Self, AocError> {
+        let (grid, []) = Self::from_lines(read_lines(input).enumerate(), [], tile)?;
+        Ok(grid)
+    }
+
+    pub fn parse_with_markers<const N: usize>(
+        input: &str,
+        markers: [char; N],
+        tile: impl FnMut(char) -> Option<T>,
+    ) -> Result<(Self, Markers<N>), AocError> {
+        Self::from_lines(read_lines(input).enumerate(), markers, tile)
+    }
+
+    // Lines come with their line numbers, so a grid that is only a part of the input
+    // still reports errors at the right place. Markers are passed to `tile` as well
+    // and only their first occurrence is recorded.
+    pub fn from_lines<'a, const N: usize>(
+        lines: impl IntoIterator<Item = (usize, &'a str)>,
+        markers: [char; N],
+        mut tile: impl FnMut(char) -> Option<T>,
+    ) -> Result<(Self, Markers<N>), AocError> {
+        let mut found = [None; N];
+        let mut cells = vec![];
+        let (mut width, mut height) = (None, 0);
+
+        for (y, line) in lines {
+            let row = height;
+            for (x, c) in line.chars().enumerate() {
+                if let Some(i) = markers.iter().position(|&m| m == c) {
+                    found[i].get_or_insert(Point::new(x, row));
+                }
+                cells.push(tile(c).ok_or_else(|| AocError::unexpected_char(y, x, c))?);
+            }
+
+            let w = *width.get_or_insert(cells.len());
+            if cells.len() != w * (row + 1) {
+                return Err(AocError::expected(format!("{w} cells")).on_line(y));
+            }
+            height += 1;
+        }
+
+        match width {
+            None | Some(0) => Err(AocError::expected("a grid")),
+            Some(width) => Ok((Self::new(width, height, cells), found)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn contains(&self, xy: Point<usize>) -> bool {
+        xy.x < self.width && xy.y < self.height
+    }
+
+    pub fn get(&self, xy: Point<usize>) -> Option<&T> {
+        self.contains(xy).then(|| &self[xy])
+    }
+
+    pub fn get_mut(&mut self, xy: Point<usize>) -> Option<&mut T> {
+        self.contains(xy).then(|| &mut self[xy])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point<usize>> + use<T> {
+        let (w, h) = (self.width, self.height);
+        (0..h).flat_map(move |y| (0..w).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn position(&self, mut f: impl FnMut(&T) -> bool) -> Option<Point<usize>> {
+        self.iter().find_map(|(xy, t)| f(t).then_some(xy))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    // All cells from `start` on, taking steps of `step` until leaving the grid.
+    pub fn line(&self, start: Point<usize>, step: Point<isize>) -> Line<'_, T> {
+        let len = std::iter::successors(self.get(start).map(|_| start), |&xy| {
+            xy.checked_add_signed(step).filter(|&next| self.contains(next))
+        })
+        .count();
+        Line { grid: self, start, step, front: 0, back: len }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.height).map(|y| self.line(Point::new(0, y), Direction::Right.offset()))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.width).map(|x| self.line(Point::new(x, 0), Direction::Down.offset()))
+    }
+
+    // Diagonals going down and to the right, starting from the top row and the left column.
+    pub fn diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let starts = (0..self.width).map(|x| (x, 0)).chain((1..self.height).map(|y| (0, y)));
+        starts.map(|start| self.line(start.into(), Point::new(1, 1)))
+    }
+
+    // Diagonals going down and to the left, starting from the top row and the right column.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let right = self.width - 1;
+        let starts =
+            (0..self.width).map(|x| (x, 0)).chain((1..self.height).map(move |y| (right, y)));
+        starts.map(|start| self.line(start.into(), Point::new(-1, 1)))
+    }
+
+    fn index_of(&self, xy: Point<usize>) -> usize {
+        assert!(self.contains(xy), "({}, {}) is outside of the grid", xy.x, xy.y);
+        xy.y * self.width + xy.x
+    }
+}
+
+#[derive(Clone)]
+pub struct Line<'a, T> {
+    grid: &'a Grid<T>,
+    start: Point<usize>,
+    step: Point<isize>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Line<'a, T> {
+    fn nth_cell(&self, i: usize) -> &'a T {
+        let Point { x, y } = self.start;
+        let Point { x: dx, y: dy } = self.step * i as isize;
+        &self.grid[Point::new(x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))]
+    }
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        (self.front < self.back).then(|| {
+            self.front += 1;
+            self.nth_cell(self.front - 1)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl<T> DoubleEndedIterator for Line<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.front < self.back).then(|| {
+            self.back -= 1;
+            self.nth_cell(self.back)
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Line<'_, T> {}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, xy: Point<usize>) -> &T {
+        &self.cells[self.index_of(xy)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, xy: Point<usize>) -> &mut T {
+        let i = self.index_of(xy);
+        &mut self.cells[i]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks_exact(self.width) {
+            row.iter().try_for_each(|t| write!(f, "{t}"))?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use super::*;
+
+    const INPUT: &str = "#S.\n.#E\n";
+
+    fn parse() -> (Grid<char>, Markers<3>) {
+        Grid::parse_with_markers(INPUT, ['S', 'E', '@'], Some).unwrap()
+    }
+
+    #[test]
+    fn parses_markers_and_displays() {
+        let (grid, markers) = parse();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(markers, [Some(Point::new(1, 0)), Some(Point::new(2, 1)), None]);
+        assert_eq!(grid.to_string(), INPUT);
+    }
+
+    #[test]
+    fn ignores_trailing_blank_lines() {
+        let (grid, _) = Grid::parse_with_markers(&format!("{INPUT}\n\r\n"), [], Some).unwrap();
+        assert_eq!(grid.to_string(), INPUT);
+        assert!(Grid::parse_with_markers("#S.\n\n.#E\n", [], Some).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one cell wide")]
+    fn rejects_zero_width() {
+        Grid::filled(0, 3, '.');
+    }
+
+    #[test]
+    fn rejects_ragged_rows_and_unknown_chars() {
+        let err = Grid::parse("..\n.\n", Some).unwrap_err();
+        assert_eq!(err.line, Some(1));
+
+        let err = Grid::parse("..\n.x\n", |c| (c == '.').then_some(c)).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn lines() {
+        let (grid, _) = parse();
+        let collect = |lines: &mut dyn Iterator<Item = Line<char>>| {
+            lines.map(|line| line.collect::<String>()).collect::<Vec<_>>()
+        };
+        assert_eq!(collect(&mut grid.rows()), ["#S.", ".#E"]);
+        assert_eq!(collect(&mut grid.columns()), ["#.", "S#", ".E"]);
+        assert_eq!(collect(&mut grid.diagonals()), ["##", "SE", ".", "."]);
+        assert_eq!(collect(&mut grid.anti_diagonals()), ["#", "S.", ".#", "E"]);
+        let line = grid.line(Point::new(2, 1), Direction::Left.offset());
+        assert_eq!(line.rev().collect::<String>(), ".#E");
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+    }
+}
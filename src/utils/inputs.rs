@@ -0,0 +1,35 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use super::{AocError, read_input};
+
+const INPUTS_ENV: &str = "AOC_INPUTS";
+
+static INPUTS_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+// Has no effect once the root has been resolved, so call it before running any task.
+pub fn set_inputs_root(root: impl Into<PathBuf>) {
+    let _ = INPUTS_ROOT.set(root.into());
+}
+
+pub fn inputs_root() -> &'static Path {
+    INPUTS_ROOT.get_or_init(|| match std::env::var_os(INPUTS_ENV) {
+        Some(root) => root.into(),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"),
+    })
+}
+
+pub fn input_path(file: impl AsRef<Path>) -> PathBuf {
+    inputs_root().join(file)
+}
+
+// Files on disk take precedence, so embedded inputs can still be overridden.
+pub fn read_named_input(file: &str, embedded: Option<&str>) -> Result<String, AocError> {
+    let path = input_path(file);
+    match embedded {
+        Some(input) if !path.is_file() => Ok(input.to_string()),
+        _ => read_input(path),
+    }
+}
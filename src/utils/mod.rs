@@ -0,0 +1,440 @@
+mod answer;
+mod cancel;
+mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+mod inputs;
+pub mod search;
+
+use std::{io::Read, path::Path};
+
+pub use answer::{Answer, ToAnswer};
+pub use cancel::{CancelToken, check_cancelled, with_cancel_token};
+pub use error::{AocError, ErrorKind, parse_number};
+pub use inputs::{input_path, inputs_root, read_named_input, set_inputs_root};
+
+pub fn read_input<P: AsRef<Path>>(filename: P) -> Result<String, AocError> {
+    std::fs::read_to_string(&filename).map_err(|err| AocError::from(err).in_file(filename.as_ref()))
+}
+
+// Blank lines at the end of the input are dropped, so every day accepts a trailing newline or two.
+pub fn read_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.trim_end_matches(['\n', '\r']).lines()
+}
+
+#[macro_export]
+macro_rules! day {
+    ($day:tt: $solution:ty,
+     part_1: {
+         examples: [$($file1:literal $(=> $expected1:expr)?),* $(,)?],
+         func: $func1:expr $(,)?
+     },
+     part_2: {
+         examples: [$($file2:literal $(=> $expected2:expr)?),* $(,)?],
+         func: $func2:expr $(,)?
+     } $(,)?
+    ) => {
+        pub const SOLUTION: $solution = $crate::utils::Day {
+            day: $day,
+            module: module_path!(),
+            embedded_answers: $crate::day!(@embed $day, "answers.txt"),
+            part_1: $crate::day!(@task $day, [$($file1 $(=> $expected1)?),*], $func1),
+            part_2: $crate::day!(@task $day, [$($file2 $(=> $expected2)?),*], $func2),
+        };
+
+        $crate::day!(@tests);
+    };
+    ($day:tt: $solution:ty,
+     parse: $parse:expr,
+     part_1: {
+         examples: [$($file1:literal $(=> $expected1:expr)?),* $(,)?],
+         func: $func1:expr $(,)?
+     },
+     part_2: {
+         examples: [$($file2:literal $(=> $expected2:expr)?),* $(,)?],
+         func: $func2:expr $(,)?
+     } $(,)?
+    ) => {
+        pub const SOLUTION: $solution = $crate::utils::ParsedDay {
+            day: $day,
+            module: module_path!(),
+            embedded_answers: $crate::day!(@embed $day, "answers.txt"),
+            parse: $parse,
+            part_1: $crate::day!(
+                @task $day,
+                [$($file1 $(=> $expected1)?),*],
+                |input| ($func1)(&($parse)(input)?)
+            ),
+            part_2: $crate::day!(
+                @task $day,
+                [$($file2 $(=> $expected2)?),*],
+                |input| ($func2)(&($parse)(input)?)
+            ),
+            solve_1: $func1,
+            solve_2: $func2,
+        };
+
+        $crate::day!(@tests);
+    };
+    (@task $day:tt, [$($file:literal $(=> $expected:expr)?),*], $func:expr) => {
+        $crate::utils::Task {
+            examples: &[$($crate::day!(@example $day, $file $(=> $expected)?)),*],
+            task: $crate::day!(@path $day, "task.txt"),
+            embedded_task: $crate::day!(@embed $day, "task.txt"),
+            func: $func,
+        }
+    };
+    (@tests) => {
+        #[cfg(test)]
+        mod example_tests {
+            use super::*;
+
+            #[test]
+            fn part_1() {
+                SOLUTION.part_1.test_examples();
+            }
+
+            #[test]
+            fn part_2() {
+                SOLUTION.part_2.test_examples();
+            }
+        }
+    };
+    (@example $day:tt, $file:literal) => {
+        $crate::utils::Example {
+            file: $crate::day!(@path $day, $file),
+            embedded: $crate::day!(@embed $day, $file),
+            expected: None,
+        }
+    };
+    (@example $day:tt, $file:literal => $expected:expr) => {
+        $crate::utils::Example {
+            file: $crate::day!(@path $day, $file),
+            embedded: $crate::day!(@embed $day, $file),
+            expected: Some(|| $expected.to_owned()),
+        }
+    };
+    (@embed $day:tt, $file:literal) => {{
+        #[cfg(feature = "embedded-inputs")]
+        let embedded = Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/inputs/",
+            $crate::day!(@path $day, $file)
+        )));
+        #[cfg(not(feature = "embedded-inputs"))]
+        let embedded = None;
+        embedded
+    }};
+    (@path $day:tt, $file:literal) => {
+        concat!($crate::day!(@dir $day), "/", $file)
+    };
+    (@dir 1) => { "day_01" };
+    (@dir 2) => { "day_02" };
+    (@dir 3) => { "day_03" };
+    (@dir 4) => { "day_04" };
+    (@dir 5) => { "day_05" };
+    (@dir 6) => { "day_06" };
+    (@dir 7) => { "day_07" };
+    (@dir 8) => { "day_08" };
+    (@dir 9) => { "day_09" };
+    (@dir 10) => { "day_10" };
+    (@dir 11) => { "day_11" };
+    (@dir 12) => { "day_12" };
+    (@dir 13) => { "day_13" };
+    (@dir 14) => { "day_14" };
+    (@dir 15) => { "day_15" };
+    (@dir 16) => { "day_16" };
+    (@dir 17) => { "day_17" };
+    (@dir 18) => { "day_18" };
+    (@dir 19) => { "day_19" };
+    (@dir 20) => { "day_20" };
+    (@dir 21) => { "day_21" };
+    (@dir 22) => { "day_22" };
+    (@dir 23) => { "day_23" };
+    (@dir 24) => { "day_24" };
+    (@dir 25) => { "day_25" };
+}
+
+pub struct Example<'a, Out> {
+    pub file: &'a str,
+    pub embedded: Option<&'a str>,
+    pub expected: Option<fn() -> Out>,
+}
+
+pub struct Task<'a, Out> {
+    pub examples: &'a [Example<'a, Out>],
+    pub task: &'a str,
+    pub embedded_task: Option<&'a str>,
+    pub func: fn(&str) -> Result<Out, AocError>,
+}
+
+impl<'a, Out> Task<'a, Out> {
+    pub fn run(&self, input: &str) -> Result<Out, AocError> {
+        (self.func)(input)
+    }
+
+    pub fn run_reader(&self, mut reader: impl Read) -> Result<Out, AocError> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        self.run(&input)
+    }
+
+    pub fn run_file(&self, filename: impl AsRef<Path>) -> Result<Out, AocError> {
+        let input = read_input(&filename)?;
+        self.run(&input).map_err(|err| err.in_file(filename.as_ref()))
+    }
+
+    pub fn run_example(&self, n: usize) -> Result<Out, AocError> {
+        self.run_named(self.examples[n].file)
+    }
+
+    pub fn run_task(&self) -> Result<Out, AocError> {
+        self.run_named(self.task)
+    }
+
+    pub fn embedded(&self, file: &str) -> Option<&'a str> {
+        match file == self.task {
+            true => self.embedded_task,
+            false => self.examples.iter().find(|example| example.file == file)?.embedded,
+        }
+    }
+
+    fn run_named(&self, file: &str) -> Result<Out, AocError> {
+        let input = read_named_input(file, self.embedded(file))?;
+        self.run(&input).map_err(|err| err.in_file(input_path(file)))
+    }
+}
+
+impl<Out: ToAnswer> Task<'_, Out> {
+    pub fn test_examples(&self) {
+        for (n, example) in self.examples.iter().enumerate() {
+            if let Some(expected) = example.expected {
+                let res = self.run_example(n).unwrap_or_else(|err| panic!("{err}"));
+                assert_eq!(res.to_answer(), expected().to_answer(), "{}", example.file);
+            }
+        }
+    }
+}
+
+pub struct Day<'a, Out1, Out2> {
+    pub day: usize,
+    pub module: &'a str,
+    pub embedded_answers: Option<&'a str>,
+    pub part_1: Task<'a, Out1>,
+    pub part_2: Task<'a, Out2>,
+}
+
+// A day that parses its input once and solves both parts from the result. `part_1` and
+// `part_2` still run from the raw input, parsing on their own.
+pub struct ParsedDay<'a, In, Out1, Out2> {
+    pub day: usize,
+    pub module: &'a str,
+    pub embedded_answers: Option<&'a str>,
+    pub parse: fn(&str) -> Result<In, AocError>,
+    pub part_1: Task<'a, Out1>,
+    pub part_2: Task<'a, Out2>,
+    pub solve_1: fn(&In) -> Result<Out1, AocError>,
+    pub solve_2: fn(&In) -> Result<Out2, AocError>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+pub trait Parsed: Send + Sync {
+    fn solve(&self, part: Part) -> Result<String, AocError>;
+}
+
+pub trait Solution: Sync {
+    fn day(&self) -> usize;
+    fn name(&self) -> &str;
+    fn examples(&self, part: Part) -> Vec<(&str, Option<String>)>;
+    fn task(&self, part: Part) -> &str;
+    fn embedded(&self, part: Part, file: &str) -> Option<&str>;
+    fn embedded_answers(&self) -> Option<&str>;
+    fn run(&self, part: Part, input: &str) -> Result<String, AocError>;
+    fn parse<'s>(&'s self, input: &str) -> Result<Box<dyn Parsed + 's>, AocError>;
+
+    fn run_file(&self, part: Part, filename: &Path) -> Result<String, AocError> {
+        let input = read_input(filename)?;
+        self.run(part, &input).map_err(|err| err.in_file(filename))
+    }
+
+    fn run_named(&self, part: Part, file: &str) -> Result<String, AocError> {
+        let input = read_named_input(file, self.embedded(part, file))?;
+        self.run(part, &input).map_err(|err| err.in_file(input_path(file)))
+    }
+
+    fn title(&self) -> String {
+        self.name()
+            .split('_')
+            .skip(1)
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map(|c| c.to_ascii_uppercase()).into_iter().chain(chars).collect()
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn run_part(&self, part: Part, file: &str) -> bool {
+        match self.run_named(part, file) {
+            Ok(res) => {
+                println!("d{:02} p{}: {res}", self.day(), part.number());
+                true
+            }
+            Err(err) => {
+                eprintln!("d{:02} p{}: error: {err}", self.day(), part.number());
+                false
+            }
+        }
+    }
+
+    fn run_part_1(&self) -> bool {
+        self.run_part(Part::One, self.task(Part::One))
+    }
+
+    fn run_part_2(&self) -> bool {
+        self.run_part(Part::Two, self.task(Part::Two))
+    }
+}
+
+fn render_examples<'a, Out: ToAnswer>(
+    examples: &[Example<'a, Out>],
+) -> Vec<(&'a str, Option<String>)> {
+    examples
+        .iter()
+        .map(|example| (example.file, example.expected.map(|f| f().to_answer().to_string())))
+        .collect()
+}
+
+struct Unparsed<'s, S>(&'s S, String);
+
+impl<S: Solution> Parsed for Unparsed<'_, S> {
+    fn solve(&self, part: Part) -> Result<String, AocError> {
+        self.0.run(part, &self.1)
+    }
+}
+
+// What `Day` and `ParsedDay` have in common, so that both implement `Solution` through the
+// same code and only differ in how they parse.
+trait DayParts: Sync {
+    type Out1: ToAnswer;
+    type Out2: ToAnswer;
+
+    // The day, its module path and the embedded answers.
+    fn header(&self) -> (usize, &str, Option<&str>);
+    fn parts(&self) -> (&Task<'_, Self::Out1>, &Task<'_, Self::Out2>);
+    fn parse_parts<'s>(&'s self, input: &str) -> Result<Box<dyn Parsed + 's>, AocError>;
+}
+
+impl<D: DayParts> Solution for D {
+    fn day(&self) -> usize {
+        self.header().0
+    }
+
+    fn name(&self) -> &str {
+        let (_, module, _) = self.header();
+        module.rsplit("::").next().unwrap_or(module)
+    }
+
+    fn examples(&self, part: Part) -> Vec<(&str, Option<String>)> {
+        match (part, self.parts()) {
+            (Part::One, (part_1, _)) => render_examples(part_1.examples),
+            (Part::Two, (_, part_2)) => render_examples(part_2.examples),
+        }
+    }
+
+    fn task(&self, part: Part) -> &str {
+        match (part, self.parts()) {
+            (Part::One, (part_1, _)) => part_1.task,
+            (Part::Two, (_, part_2)) => part_2.task,
+        }
+    }
+
+    fn embedded(&self, part: Part, file: &str) -> Option<&str> {
+        match (part, self.parts()) {
+            (Part::One, (part_1, _)) => part_1.embedded(file),
+            (Part::Two, (_, part_2)) => part_2.embedded(file),
+        }
+    }
+
+    fn embedded_answers(&self) -> Option<&str> {
+        self.header().2
+    }
+
+    fn run(&self, part: Part, input: &str) -> Result<String, AocError> {
+        match (part, self.parts()) {
+            (Part::One, (part_1, _)) => part_1.run(input).map(|res| res.to_answer().to_string()),
+            (Part::Two, (_, part_2)) => part_2.run(input).map(|res| res.to_answer().to_string()),
+        }
+    }
+
+    fn parse<'s>(&'s self, input: &str) -> Result<Box<dyn Parsed + 's>, AocError> {
+        self.parse_parts(input)
+    }
+}
+
+impl<Out1: ToAnswer, Out2: ToAnswer> DayParts for Day<'_, Out1, Out2> {
+    type Out1 = Out1;
+    type Out2 = Out2;
+
+    fn header(&self) -> (usize, &str, Option<&str>) {
+        (self.day, self.module, self.embedded_answers)
+    }
+
+    fn parts(&self) -> (&Task<'_, Out1>, &Task<'_, Out2>) {
+        (&self.part_1, &self.part_2)
+    }
+
+    fn parse_parts<'s>(&'s self, input: &str) -> Result<Box<dyn Parsed + 's>, AocError> {
+        Ok(Box::new(Unparsed(self, input.to_owned())))
+    }
+}
+
+struct ParsedInput<'s, 'a, In, Out1, Out2>(&'s ParsedDay<'a, In, Out1, Out2>, In);
+
+impl<In: Send + Sync, Out1: ToAnswer, Out2: ToAnswer> Parsed
+    for ParsedInput<'_, '_, In, Out1, Out2>
+{
+    fn solve(&self, part: Part) -> Result<String, AocError> {
+        match part {
+            Part::One => (self.0.solve_1)(&self.1).map(|res| res.to_answer().to_string()),
+            Part::Two => (self.0.solve_2)(&self.1).map(|res| res.to_answer().to_string()),
+        }
+    }
+}
+
+impl<In, Out1, Out2> DayParts for ParsedDay<'_, In, Out1, Out2>
+where
+    In: Send + Sync,
+    Out1: ToAnswer,
+    Out2: ToAnswer,
+{
+    type Out1 = Out1;
+    type Out2 = Out2;
+
+    fn header(&self) -> (usize, &str, Option<&str>) {
+        (self.day, self.module, self.embedded_answers)
+    }
+
+    fn parts(&self) -> (&Task<'_, Out1>, &Task<'_, Out2>) {
+        (&self.part_1, &self.part_2)
+    }
+
+    fn parse_parts<'s>(&'s self, input: &str) -> Result<Box<dyn Parsed + 's>, AocError> {
+        Ok(Box::new(ParsedInput(self, (self.parse)(input)?)))
+    }
+}
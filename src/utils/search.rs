@@ -0,0 +1,219 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+// Yields every reachable state once, in the order it was discovered, with its distance
+// from the closest start.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> impl Iterator<Item = (S, usize)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::new();
+    let mut q: VecDeque<_> =
+        starts.into_iter().filter(|s| seen.insert(s.clone())).map(|s| (s, 0)).collect();
+
+    std::iter::from_fn(move || {
+        let (state, distance) = q.pop_front()?;
+        for next in neighbours(&state) {
+            if seen.insert(next.clone()) {
+                q.push_back((next, distance + 1));
+            }
+        }
+        Some((state, distance))
+    })
+}
+
+pub struct ShortestPaths<S, C> {
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> ShortestPaths<S, C> {
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn path_to(&self, goal: &S) -> Option<Vec<S>> {
+        self.costs.contains_key(goal).then(|| {
+            reconstruct(goal.clone(), |s| self.predecessors.get(s).and_then(|p| p.first()).cloned())
+        })
+    }
+
+    // Every state that lies on some shortest path to one of `goals`.
+    pub fn on_shortest_paths(&self, goals: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut states = HashSet::new();
+        let mut stack: Vec<_> = goals.into_iter().filter(|g| self.costs.contains_key(g)).collect();
+
+        while let Some(state) = stack.pop() {
+            if let Some(prev) = self.predecessors.get(&state) {
+                stack.extend(prev.iter().filter(|&p| !states.contains(p)).cloned());
+            }
+            states.insert(state);
+        }
+
+        states
+    }
+}
+
+// Explores everything reachable, keeping all equally cheap predecessors of every state.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> ShortestPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut q = BinaryHeap::new();
+
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        q.push(Entry { priority: C::default(), cost: C::default(), state: start });
+    }
+
+    while let Some(Entry { cost, state, .. }) = q.pop() {
+        if costs.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let cost = cost + step;
+            match costs.get(&next) {
+                Some(&best) if best < cost => {}
+                Some(&best) if best == cost => {
+                    predecessors.entry(next).or_default().push(state.clone());
+                }
+                _ => {
+                    costs.insert(next.clone(), cost);
+                    predecessors.insert(next.clone(), vec![state.clone()]);
+                    q.push(Entry { priority: cost, cost, state: next });
+                }
+            }
+        }
+    }
+
+    ShortestPaths { costs, predecessors }
+}
+
+// `heuristic` must never overestimate the remaining cost for the result to be the cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut parents = HashMap::new();
+    let mut q =
+        BinaryHeap::from([Entry { priority: heuristic(&start), cost: C::default(), state: start }]);
+
+    while let Some(Entry { cost, state, .. }) = q.pop() {
+        if is_goal(&state) {
+            return Some((cost, reconstruct(state, |s| parents.get(s).cloned())));
+        }
+        if costs.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+
+        for (next, step) in neighbours(&state) {
+            let cost = cost + step;
+            if costs.get(&next).is_none_or(|&best| cost < best) {
+                costs.insert(next.clone(), cost);
+                parents.insert(next.clone(), state.clone());
+                q.push(Entry { priority: cost + heuristic(&next), cost, state: next });
+            }
+        }
+    }
+
+    None
+}
+
+fn reconstruct<S>(goal: S, mut parent: impl FnMut(&S) -> Option<S>) -> Vec<S> {
+    let mut path: Vec<_> = std::iter::successors(Some(goal), |s| parent(s)).collect();
+    path.reverse();
+    path
+}
+
+// Orders the heap by `priority` only, cheapest first.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+#[cfg(test)]
+mod search_tests {
+    use super::*;
+
+    // 0 - 1 - 3
+    // |       |
+    // 2 - 4 - 5, every edge costs 1
+    fn edges(n: &u32) -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(4, 1)],
+            3 => vec![(5, 1)],
+            4 => vec![(5, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_distances_in_discovery_order() {
+        let res: Vec<_> = bfs([0], |n| edges(n).into_iter().map(|(n, _)| n)).collect();
+        assert_eq!(res, [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2), (5, 3)]);
+    }
+
+    #[test]
+    fn dijkstra_keeps_all_shortest_paths() {
+        let paths = dijkstra([0], edges);
+        assert_eq!(paths.cost(&5), Some(3));
+        assert_eq!(paths.path_to(&5), Some(vec![0, 1, 3, 5]));
+        assert_eq!(paths.on_shortest_paths([5]), HashSet::from([0, 1, 2, 3, 4, 5]));
+        assert_eq!(paths.on_shortest_paths([4]), HashSet::from([0, 2, 4]));
+    }
+
+    #[test]
+    fn astar_finds_cheapest_path() {
+        let (cost, path) = astar(0, edges, |&n| u32::from(n != 5), |&n| n == 5).unwrap();
+        assert_eq!(cost, 3);
+        assert_eq!((path.first(), path.last(), path.len()), (Some(&0), Some(&5), 4));
+        assert_eq!(astar(3, edges, |_| 0, |&n| n == 0), None);
+    }
+}